@@ -1,10 +1,105 @@
 #![feature(allocator_api)]
 #![allow(unused)]
 mod md_line_purifier;
+mod md_abbreviation;
+mod md_attributes;
 mod md_block;
+mod md_container;
+mod md_front_matter;
+mod md_include;
 mod md_inline_parser;
 mod md_line_reader;
+mod md_options;
+mod md_renderer;
+mod md_section;
+mod md_tasks;
 
-pub fn parser_md(data: String) {
-    println!("Yet not implemented!");
+pub use md_attributes::Attributes;
+pub use md_block::{AlertKind, DefinitionItem, DiagramKind, ListItem, MdBlock, TableRow};
+pub use md_container::ContainerRegistry;
+pub use md_front_matter::{FrontMatter, FrontMatterKind, FrontMatterValue};
+pub use md_include::{FileIncludeResolver, IncludeResolver};
+pub use md_inline_parser::{md_inline::MdInline, md_string::MdString, references::Reference};
+pub use md_options::ParserOptions;
+pub use md_renderer::{
+    diagram_renderer::DiagramRenderer,
+    highlighter::{highlight, TokenKind},
+    html_renderer::{EmojiStyle, HtmlRenderer},
+    markdown_writer::MarkdownWriter,
+    reference_resolver::ReferenceResolver,
+    terminal_renderer::TerminalRenderer,
+    wiki_resolver::{DefaultWikiResolver, WikiLinkResolver},
+};
+pub use md_section::{Section, Sections};
+pub use md_tasks::{list_tasks, set_task, toggle_task, Task};
+
+use md_block::MdBlockParser;
+use md_line_purifier::PurifiedMdLine;
+
+///
+/// parsed markdown, front matter is kept apart from the blocks
+///
+#[derive(Debug, PartialEq)]
+pub struct Document {
+    pub front_matter: Option<FrontMatter>,
+    pub blocks: Vec<MdBlock>,
+}
+
+pub fn parser_md(data: String) -> Document {
+    parser_md_with_options(data, &ParserOptions::default())
+}
+
+pub fn parser_md_with_options(data: String, options: &ParserOptions) -> Document {
+    let extracted = options
+        .front_matter
+        .then(|| md_front_matter::extract(&data))
+        .flatten();
+    let (front_matter, skip) = match extracted {
+        Some((front_matter, line_count)) => (Some(front_matter), line_count),
+        None => (None, 0),
+    };
+    let lines = md_line_reader::to_mdlines(
+        data.lines().skip(skip).map(String::from).collect(),
+        options,
+    );
+    let lines: Vec<PurifiedMdLine> = lines
+        .into_iter()
+        .map(|line| PurifiedMdLine::purify_with(line, options))
+        .collect();
+    // abbreviations apply to the whole document, even above their definition
+    let abbrs = md_abbreviation::collect(&lines);
+    let mut blocks = MdBlockParser::parse(lines, options).blocks();
+    if !abbrs.is_empty() {
+        md_abbreviation::apply(&mut blocks, &abbrs);
+    }
+    Document {
+        front_matter,
+        blocks,
+    }
+}
+
+///
+/// like `parser_md_with_options`, with every `{{#include file}}` replaced
+/// by the blocks of the file. `path` is where `data` was read from,
+/// included paths are relative to it.
+///
+/// `lines=3-10` takes only those lines of the file, `section="Title"` only
+/// that heading and what's under it, and `shift=1` moves its headings
+/// down a level. files which can't be read, or which include themselves
+/// again, are left as `MdBlock::Include`.
+///
+pub fn parser_md_with_includes(
+    data: String,
+    path: &str,
+    options: &ParserOptions,
+    resolver: &dyn IncludeResolver,
+) -> Document {
+    let mut document = parser_md_with_options(data, options);
+    let mut stack = vec![path.to_string()];
+    md_include::expand(&mut document.blocks, &mut stack, options, resolver);
+    document
+}
+
+pub fn md_to_html(data: String) -> String {
+    HtmlRenderer::new().render(&parser_md(data).blocks)
 }
@@ -0,0 +1,196 @@
+use crate::{
+    md_block::{DefinitionItem, ListItem, MdBlock},
+    md_inline_parser::{md_inline::MdInline, md_string::MdString},
+    md_line_purifier::PurifiedMdLine,
+};
+
+///
+/// `*[abbr]: title` definitions of the document, a later
+/// definition of the same abbreviation replaces the earlier one.
+///
+/// longest ones come first, so they are matched before
+/// the shorter ones they start with.
+///
+pub(crate) fn collect(lines: &[PurifiedMdLine]) -> Vec<(String, String)> {
+    let mut abbrs: Vec<(String, String)> = vec![];
+    for line in lines {
+        let mut line = line;
+        // `> *[abbr]: title` and an indented one define it too
+        loop {
+            match line {
+                PurifiedMdLine::Quote { inside_md, .. } => line = inside_md,
+                PurifiedMdLine::TabbedLine { text, .. } => line = text,
+                _ => break,
+            }
+        }
+        if let PurifiedMdLine::Abbreviation { abbr, title } = line {
+            abbrs.retain(|(other, _)| other != abbr);
+            abbrs.push((abbr.clone(), title.clone()));
+        }
+    }
+    abbrs.sort_by_key(|(abbr, _)| std::cmp::Reverse(abbr.len()));
+    abbrs
+}
+
+///
+/// wraps every whole word occurrence of the abbreviations
+/// in `blocks` into an `Abbr`, code and link urls are left alone.
+///
+pub(crate) fn apply(blocks: &mut [MdBlock], abbrs: &[(String, String)]) {
+    for block in blocks {
+        match block {
+            MdBlock::Head { data, .. } | MdBlock::Parah(data) => apply_inline(data, abbrs),
+            MdBlock::BlockQuote { data } | MdBlock::Container { children: data, .. } => {
+                apply(data, abbrs)
+            }
+            MdBlock::Alert { title, data, .. } => {
+                if let Some(title) = title {
+                    apply_inline(title, abbrs);
+                }
+                apply(data, abbrs);
+            }
+            MdBlock::OList { data } | MdBlock::UList { data } => {
+                for ListItem { data, children, .. } in data {
+                    apply_inline(data, abbrs);
+                    apply(children, abbrs);
+                }
+            }
+            MdBlock::Table { data } => {
+                for cell in data.iter_mut().flat_map(|(_, cells)| cells) {
+                    apply_inline(cell, abbrs);
+                }
+            }
+            MdBlock::LineBlock(data) => {
+                for line in data {
+                    apply_inline(line, abbrs);
+                }
+            }
+            MdBlock::Definition { data } => {
+                for DefinitionItem { terms, defs } in data {
+                    for term in terms {
+                        apply_inline(term, abbrs);
+                    }
+                    for def in defs {
+                        apply(def, abbrs);
+                    }
+                }
+            }
+            MdBlock::Image { caption, .. } => {
+                if let Some(caption) = caption {
+                    apply_inline(caption, abbrs);
+                }
+            }
+            MdBlock::CodeBlock { .. }
+            | MdBlock::Diagram { .. }
+            | MdBlock::Math(_)
+            | MdBlock::Include { .. }
+            | MdBlock::Comment(_)
+            | MdBlock::HR
+            | MdBlock::EmptyLine => (),
+        }
+    }
+}
+
+fn apply_inline(md_string: &mut MdString, abbrs: &[(String, String)]) {
+    for inline in md_string.iter_mut() {
+        match inline {
+            MdInline::Bold(s)
+            | MdInline::Italic(s)
+            | MdInline::BoldItalic(s)
+            | MdInline::Strike(s)
+            | MdInline::Highlight(s)
+            | MdInline::Sub(s)
+            | MdInline::Super(s)
+            | MdInline::LinkText(s) => apply_inline(s, abbrs),
+            _ => (),
+        }
+    }
+    let mut out = MdString::new();
+    for inline in std::mem::take(md_string).into_vec() {
+        match inline {
+            MdInline::InlineString(text) => split_text(&text, abbrs, &mut out),
+            inline => out.push(inline),
+        }
+    }
+    *md_string = out;
+}
+
+/// `text` with its abbreviations as `Abbr`s, pushed to `out`
+fn split_text(text: &str, abbrs: &[(String, String)], out: &mut MdString) {
+    let mut plain = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        let at_word_start = !text[..pos].chars().next_back().is_some_and(is_word_char);
+        let found = at_word_start
+            .then(|| {
+                abbrs.iter().find(|(abbr, _)| {
+                    text[pos..].starts_with(abbr.as_str())
+                        && !text[pos + abbr.len()..].chars().next().is_some_and(is_word_char)
+                })
+            })
+            .flatten();
+        match found {
+            Some((abbr, title)) => {
+                if plain < pos {
+                    out.push_str(&text[plain..pos]);
+                }
+                out.push(MdInline::Abbr {
+                    text: abbr.clone(),
+                    title: title.clone(),
+                });
+                pos += abbr.len();
+                plain = pos;
+            }
+            None => pos += text[pos..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if plain < text.len() {
+        out.push_str(&text[plain..]);
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+#[cfg(test)]
+mod abbreviation_testing {
+    use crate::{
+        md_block::MdBlock,
+        md_inline_parser::{md_inline::MdInline::*, md_string::MdString},
+        md_to_html, parser_md,
+    };
+
+    #[test]
+    fn whole_words() {
+        let data = "HTML and XHTML, *HTML5* `HTML` HTML-ish\n\n\
+                    *[HTML]: Hyper Text Markup Language\n*[HTML]: Hypertext Markup Language";
+        let html = || Abbr {
+            text: "HTML".to_string(),
+            title: "Hypertext Markup Language".to_string(),
+        };
+        assert_eq!(
+            parser_md(data.to_string()).blocks,
+            vec![MdBlock::Parah(MdString::from_vec(vec![
+                html(),
+                InlineString(" and XHTML, ".to_string()),
+                Italic(MdString::from_vec(vec![InlineString("HTML5".to_string())])),
+                InlineString(" ".to_string()),
+                Code(MdString::from_vec(vec![InlineString("HTML".to_string())])),
+                InlineString(" ".to_string()),
+                html(),
+                InlineString("-ish".to_string()),
+            ]))]
+        );
+    }
+
+    #[test]
+    fn captions_and_indented() {
+        let data = "![a](b.png)\n*The W3C*\n\n\t*[W3C]: World Wide Web Consortium";
+        assert_eq!(
+            md_to_html(data.to_string()),
+            "<figure>\n<img src=\"b.png\" alt=\"a\" />\n<figcaption>The \
+             <abbr title=\"World Wide Web Consortium\">W3C</abbr></figcaption>\n</figure>\n"
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+
+///
+/// attributes like `#id .class key=value "word"`,
+/// values can be quoted to have spaces in them.
+///
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Attributes {
+    pub id: Option<String>,
+    pub classes: Vec<String>,
+    /// `key=value` pairs, in the order they were written
+    pub pairs: Vec<(String, String)>,
+    /// values without a key, like the `"Title"` of `::: warning "Title"`
+    pub args: Vec<String>,
+}
+
+impl Attributes {
+    pub fn parse(data: &str) -> Attributes {
+        let mut attrs = Attributes::default();
+        for word in split_words(data) {
+            if word.starts_with(['"', '\'']) {
+                attrs.args.push(unquote(&word));
+            } else if let Some(id) = word.strip_prefix('#') {
+                attrs.id = Some(id.to_string());
+            } else if let Some(class) = word.strip_prefix('.') {
+                attrs.classes.push(class.to_string());
+            } else if let Some((key, value)) = word.split_once('=') {
+                attrs.pairs.push((key.to_string(), unquote(value)));
+            } else {
+                attrs.args.push(word);
+            }
+        }
+        attrs
+    }
+
+    ///
+    /// attributes in braces, `{#id .class key=value}`, `None` if
+    /// `data` isn't that or if anything inside is not an attribute.
+    ///
+    pub fn from_braces(data: &str) -> Option<Attributes> {
+        let inner = data.strip_prefix('{')?.strip_suffix('}')?;
+        let attrs = Attributes::parse(inner);
+        let valid = !attrs.is_empty()
+            && attrs.args.is_empty()
+            && attrs.id.as_ref().is_none_or(|id| !id.is_empty())
+            && attrs.classes.iter().all(|class| !class.is_empty())
+            && attrs.pairs.iter().all(|(key, _)| !key.is_empty());
+        valid.then_some(attrs)
+    }
+
+    /// value of the first pair with `key`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.classes.is_empty() && self.pairs.is_empty() && self.args.is_empty()
+    }
+}
+
+/// writes attributes back the way they are parsed
+impl fmt::Display for Attributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words = vec![];
+        if let Some(id) = &self.id {
+            words.push(format!("#{}", id));
+        }
+        for class in &self.classes {
+            words.push(format!(".{}", class));
+        }
+        for (key, value) in &self.pairs {
+            words.push(format!("{}={}", key, quote(value)));
+        }
+        for arg in &self.args {
+            words.push(quote(arg));
+        }
+        f.write_str(&words.join(" "))
+    }
+}
+
+/// words split on whitespace, which isn't inside quotes
+fn split_words(data: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut quote = None;
+    let mut chars = data.chars();
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (Some(_), '\\') => {
+                word.push(ch);
+                word.extend(chars.next());
+            }
+            (Some(q), ch) if ch == q => {
+                quote = None;
+                word.push(ch);
+            }
+            (None, '"' | '\'') => {
+                quote = Some(ch);
+                word.push(ch);
+            }
+            (None, ch) if ch.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            (_, ch) => word.push(ch),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// removes the quotes around `value`, and backslashes inside them
+fn unquote(value: &str) -> String {
+    let Some(q) = value.chars().next().filter(|q| matches!(q, '"' | '\'')) else {
+        return value.to_string();
+    };
+    let inner = value[1..].strip_suffix(q).unwrap_or(&value[1..]);
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            unquoted.extend(chars.next());
+        } else {
+            unquoted.push(ch);
+        }
+    }
+    unquoted
+}
+
+fn quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && !value
+            .chars()
+            .any(|ch| ch.is_whitespace() || matches!(ch, '"' | '\'' | '\\' | '{' | '}'));
+    if plain {
+        value.to_string()
+    } else {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod attributes_testing {
+    use super::*;
+
+    #[test]
+    fn parse_all_kinds() {
+        let attrs = Attributes::parse(r#"#top .a .b key=value title="Big \"one\"" 'x y' bare"#);
+        assert_eq!(
+            attrs,
+            Attributes {
+                id: Some("top".to_string()),
+                classes: vec!["a".to_string(), "b".to_string()],
+                pairs: vec![
+                    ("key".to_string(), "value".to_string()),
+                    ("title".to_string(), "Big \"one\"".to_string()),
+                ],
+                args: vec!["x y".to_string(), "bare".to_string()],
+            }
+        );
+        assert_eq!(attrs.get("title"), Some("Big \"one\""));
+        assert_eq!(Attributes::parse(&attrs.to_string()), attrs);
+        assert!(Attributes::parse("  ").is_empty());
+
+        assert_eq!(Attributes::from_braces("{.a}").unwrap().classes, vec!["a".to_string()]);
+        assert_eq!(Attributes::from_braces("{# a}"), None);
+        assert_eq!(Attributes::from_braces("{}"), None);
+        assert_eq!(Attributes::from_braces(".a"), None);
+    }
+}
@@ -1,7 +1,15 @@
-use crate::{md_inline_parser::md_string::MdString, md_line_purifier::PurifiedMdLine};
+use std::collections::VecDeque;
 
-#[derive(Debug)]
-pub(crate) enum TableRow {
+use crate::{
+    md_attributes::Attributes,
+    md_inline_parser::{md_inline::MdInline, md_string::MdString},
+    md_line_purifier::PurifiedMdLine,
+    md_line_reader::to_mdline_with,
+    md_options::ParserOptions,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum TableRow {
     Heading,
     Data,
 }
@@ -12,59 +20,1035 @@ pub(crate) enum TableRow {
 /// blocks will hold multiple lines at once whereever the lines are related
 /// blocks will also convert all the strings to MdString.
 ///
-#[derive(Debug)]
-pub(crate) enum MdBlock {
+#[derive(Debug, PartialEq)]
+pub enum MdBlock {
     Head {
         level: u8,
-        id: String,
+        /// `{#id .class}` after the title
+        attrs: Attributes,
         data: MdString,
     },
     BlockQuote {
-        data: Vec<Box<MdBlock>>,
+        data: Vec<MdBlock>,
+    },
+    /// quote starting with `[!NOTE]`, `[!TIP]` ..., the way GitHub does
+    Alert {
+        kind: AlertKind,
+        /// text after the marker, if there is any
+        title: Option<MdString>,
+        data: Vec<MdBlock>,
     },
     OList {
-        data: Vec<MdString>,
+        data: Vec<ListItem>,
     },
     UList {
-        data: Vec<MdString>,
+        data: Vec<ListItem>,
     },
+    /// image alone on its line, a figure when it has a caption
     Image {
         alt_text: String,
         link_text: String,
+        /// `"title"` after the url
+        title: Option<String>,
+        /// italic line right after the image, or else the title
+        caption: Option<MdString>,
+        /// `=WxH` size is in here as `width` and `height`
+        attrs: Attributes,
     },
     Table {
         data: Vec<(TableRow, Vec<MdString>)>
     },
+    /// `| line` lines, each one kept as its own line,
+    /// leading spaces are turned into non-breaking ones.
+    LineBlock(Vec<MdString>),
     Definition {
-        term: String,
-        defs: Vec<MdString>,
+        data: Vec<DefinitionItem>,
+    },
+    CodeBlock {
+        /// first word of the info string, or first class of `{.lang}`
+        lang: Option<String>,
+        attrs: Attributes,
+        code: String,
+    },
+    /// code block in `mermaid`, `dot` or `plantuml`
+    Diagram {
+        kind: DiagramKind,
+        attrs: Attributes,
+        source: String,
+    },
+    /// tex in between `$$` fences
+    Math(String),
+    /// `<!-- -->` or `%% %%` comment on lines of its own, as it was written
+    Comment(String),
+    /// `{{#include path}}` left as it is, `parser_md_with_includes`
+    /// replaces it with the blocks of the file.
+    Include {
+        path: String,
+        /// `lines=3-10`, `section="Title"` and `shift=1`
+        attrs: Attributes,
     },
-    TaskedLine {
-        done: bool,
-        tast_text: MdString,
+    /// blocks in between `::: name attrs` and `:::`,
+    /// it's up to the application what they mean.
+    Container {
+        name: String,
+        attrs: Attributes,
+        children: Vec<MdBlock>,
     },
-    CodeBlock(String),
-    Parah(Vec<MdString>),
+    /// all lines of a paragraph, joined by `SoftBreak`/`HardBreak`
+    Parah(MdString),
     HR,
     EmptyLine,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlertKind {
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+}
+
+impl AlertKind {
+    /// name of the kind as it's shown, `Note`, `Tip` ...
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertKind::Note => "Note",
+            AlertKind::Tip => "Tip",
+            AlertKind::Important => "Important",
+            AlertKind::Warning => "Warning",
+            AlertKind::Caution => "Caution",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<AlertKind> {
+        [
+            AlertKind::Note,
+            AlertKind::Tip,
+            AlertKind::Important,
+            AlertKind::Warning,
+            AlertKind::Caution,
+        ]
+        .into_iter()
+        .find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiagramKind {
+    Mermaid,
+    /// Graphviz
+    Dot,
+    PlantUml,
+}
+
+impl DiagramKind {
+    /// language of the code block, as it's written after the fence
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagramKind::Mermaid => "mermaid",
+            DiagramKind::Dot => "dot",
+            DiagramKind::PlantUml => "plantuml",
+        }
+    }
+
+    fn from_lang(lang: &str) -> Option<DiagramKind> {
+        [DiagramKind::Mermaid, DiagramKind::Dot, DiagramKind::PlantUml]
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(lang))
+    }
+}
+
+///
+/// item of `OList` or `UList`, nested lists and any
+/// other indented blocks go in its children.
+///
+#[derive(Debug, PartialEq)]
+pub struct ListItem {
+    /// `Some(done)` when item is a task
+    pub task: Option<bool>,
+    pub data: MdString,
+    pub children: Vec<MdBlock>,
+}
+
+///
+/// one entry of a definition list,
+/// every definition can have multiple blocks in it.
+///
+#[derive(Debug, PartialEq)]
+pub struct DefinitionItem {
+    pub terms: Vec<MdString>,
+    pub defs: Vec<Vec<MdBlock>>,
+}
+
+///
+/// lines which are yet to be parsed,
+/// can look ahead as far as needed.
+///
+struct Lines {
+    lines: VecDeque<PurifiedMdLine>,
+}
+
+impl Iterator for Lines {
+    type Item = PurifiedMdLine;
+
+    fn next(&mut self) -> Option<PurifiedMdLine> {
+        self.lines.pop_front()
+    }
+}
+
+impl Lines {
+    fn peek(&self) -> Option<&PurifiedMdLine> {
+        self.lines.front()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&PurifiedMdLine> {
+        self.lines.get(n)
+    }
+
+    fn next_if(&mut self, f: impl FnOnce(&PurifiedMdLine) -> bool) -> Option<PurifiedMdLine> {
+        match self.peek() {
+            Some(line) if f(line) => self.next(),
+            _ => None,
+        }
+    }
+
+    /// number of `EmptyLine`s starting at `from`
+    fn blanks_at(&self, from: usize) -> usize {
+        self.lines
+            .iter()
+            .skip(from)
+            .take_while(|line| matches!(line, PurifiedMdLine::EmptyLine))
+            .count()
+    }
+}
 
 ///
 /// takes `PurifiedMdLine`s and converts it to Vec of `MdBlock`
 ///
 pub(crate) struct MdBlockParser {
     blocks: Vec<MdBlock>,
+    options: ParserOptions,
 }
 
 impl MdBlockParser {
-    pub fn new() -> Self {
+    pub fn new(options: &ParserOptions) -> Self {
         MdBlockParser {
-            blocks: Vec::new()
+            blocks: Vec::new(),
+            options: options.clone(),
         }
     }
 
-    pub fn parse(lines: Vec<PurifiedMdLine>) -> Self {
-        unimplemented!()
+    pub fn parse(lines: Vec<PurifiedMdLine>, options: &ParserOptions) -> Self {
+        let mut parser = MdBlockParser::new(options);
+        let mut lines = Lines {
+            lines: lines.into(),
+        };
+        while let Some(line) = lines.next() {
+            parser.parse_line(line, &mut lines);
+        }
+        parser
+    }
+
+    pub fn blocks(self) -> Vec<MdBlock> {
+        self.blocks
+    }
+
+    ///
+    /// converts `line` to a block, takes any following lines
+    /// which belong to that same block from `lines`.
+    ///
+    fn parse_line(&mut self, line: PurifiedMdLine, lines: &mut Lines) {
+        match line {
+            PurifiedMdLine::Head { title, level, attrs } => self.blocks.push(MdBlock::Head {
+                level,
+                attrs,
+                data: MdString::from_string_with(title, &self.options),
+            }),
+            PurifiedMdLine::Quote { nest_level, inside_md } => {
+                let mut inner = vec![unquote(nest_level, *inside_md)];
+                loop {
+                    // lazy continuation only works while inside a paragraph
+                    let continues = match lines.peek() {
+                        Some(PurifiedMdLine::Quote { .. }) => true,
+                        Some(line) if lazy_text(line).is_some() => {
+                            inner.last().is_some_and(is_text)
+                        }
+                        _ => false,
+                    };
+                    if !continues {
+                        break;
+                    }
+                    match lines.next().unwrap() {
+                        PurifiedMdLine::Quote { nest_level, inside_md } => {
+                            inner.push(unquote(nest_level, *inside_md))
+                        }
+                        line => inner.push(line),
+                    }
+                }
+                let alert = match inner.first() {
+                    Some(PurifiedMdLine::Text(s)) if self.options.alerts => alert_marker(s),
+                    _ => None,
+                };
+                match alert {
+                    Some((kind, title)) => {
+                        // marker line isn't part of the content
+                        inner.remove(0);
+                        self.blocks.push(MdBlock::Alert {
+                            kind,
+                            title: title.map(|title| MdString::from_string_with(title, &self.options)),
+                            data: MdBlockParser::parse(inner, &self.options).blocks(),
+                        });
+                    }
+                    None => self.blocks.push(MdBlock::BlockQuote {
+                        data: MdBlockParser::parse(inner, &self.options).blocks(),
+                    }),
+                }
+            }
+            PurifiedMdLine::OList { list_text, .. } => {
+                let item = self.list_item(None, list_text, lines);
+                self.push_list_item(true, item);
+            }
+            PurifiedMdLine::UList { list_text } => {
+                let item = self.list_item(None, list_text, lines);
+                self.push_list_item(false, item);
+            }
+            PurifiedMdLine::TaskedLine {
+                task_text,
+                done,
+                list_number,
+            } => {
+                let item = self.list_item(Some(done), task_text, lines);
+                self.push_list_item(list_number.is_some(), item);
+            }
+            PurifiedMdLine::Image {
+                alt_text,
+                link_text,
+                title,
+                attrs,
+            } => {
+                let caption = match lines.peek().and_then(|line| self.italic_line(line)) {
+                    Some(caption) => {
+                        lines.next();
+                        Some(caption)
+                    }
+                    None => title
+                        .clone()
+                        .map(|title| MdString::from_string_with(title, &self.options)),
+                };
+                self.blocks.push(MdBlock::Image {
+                    alt_text,
+                    link_text,
+                    title,
+                    caption,
+                    attrs,
+                })
+            }
+            PurifiedMdLine::Table { row } => {
+                let mut rows = vec![row];
+                while let Some(PurifiedMdLine::Table { row }) =
+                    lines.next_if(|line| matches!(line, PurifiedMdLine::Table { .. }))
+                {
+                    rows.push(row);
+                }
+                self.blocks.push(table(rows, &self.options));
+            }
+            PurifiedMdLine::LineBlock(line) => {
+                let mut block = vec![self.line_block_line(line)];
+                while let Some(PurifiedMdLine::LineBlock(line)) =
+                    lines.next_if(|line| matches!(line, PurifiedMdLine::LineBlock(_)))
+                {
+                    block.push(self.line_block_line(line));
+                }
+                self.blocks.push(MdBlock::LineBlock(block));
+            }
+            PurifiedMdLine::Definition { def_text } => {
+                // definition without any term
+                self.definition_list(vec![], Some(def_text), lines)
+            }
+            PurifiedMdLine::TabbedLine { text, .. } => self.parse_line(*text, lines),
+            PurifiedMdLine::Text(s) | PurifiedMdLine::FailedText(s) => {
+                let mut parah = vec![s];
+                while let Some(s) = next_lazy_text(lines) {
+                    parah.push(s);
+                }
+                // text lines right before a definition are its terms
+                if let Some(PurifiedMdLine::Definition { .. }) =
+                    lines.peek_nth(lines.blanks_at(0))
+                {
+                    self.definition_list(parah, None, lines);
+                } else {
+                    self.blocks.push(MdBlock::Parah(MdString::from_string_with(
+                        join_lines(&parah),
+                        &self.options,
+                    )));
+                }
+            }
+            PurifiedMdLine::CodeBlock { lang, attrs } => {
+                let code = fenced_lines(lines);
+                let diagram = lang
+                    .as_deref()
+                    .and_then(DiagramKind::from_lang)
+                    .filter(|_| self.options.diagrams);
+                match diagram {
+                    Some(kind) => self.blocks.push(MdBlock::Diagram {
+                        kind,
+                        attrs,
+                        source: code,
+                    }),
+                    None => self.blocks.push(MdBlock::CodeBlock { lang, attrs, code }),
+                }
+            }
+            PurifiedMdLine::MathBlock => {
+                let tex = fenced_lines(lines);
+                self.blocks.push(MdBlock::Math(tex));
+            }
+            PurifiedMdLine::ContainerStart { name, attrs } => {
+                // lines until the matching `:::`, or the end
+                let mut inner = vec![];
+                let mut depth = 0;
+                for line in lines.by_ref() {
+                    match line {
+                        PurifiedMdLine::ContainerStart { .. } => depth += 1,
+                        PurifiedMdLine::ContainerEnd if depth == 0 => break,
+                        PurifiedMdLine::ContainerEnd => depth -= 1,
+                        _ => (),
+                    }
+                    inner.push(line);
+                }
+                self.blocks.push(MdBlock::Container {
+                    name,
+                    attrs,
+                    children: MdBlockParser::parse(inner, &self.options).blocks(),
+                });
+            }
+            // closes nothing
+            PurifiedMdLine::ContainerEnd => (),
+            // collected for the whole document before parsing
+            PurifiedMdLine::Abbreviation { .. } => (),
+            PurifiedMdLine::Comment(first) => {
+                // lines after it, till it closes
+                let mut comment = vec![first];
+                while let Some(PurifiedMdLine::CodeLine(s)) =
+                    lines.next_if(|line| matches!(line, PurifiedMdLine::CodeLine(_)))
+                {
+                    comment.push(s);
+                }
+                self.blocks.push(MdBlock::Comment(comment.join("\n")));
+            }
+            PurifiedMdLine::Include { path, attrs } => {
+                self.blocks.push(MdBlock::Include { path, attrs })
+            }
+            PurifiedMdLine::CodeLine(s) => self.blocks.push(MdBlock::CodeBlock {
+                lang: None,
+                attrs: Attributes::default(),
+                code: s,
+            }),
+            PurifiedMdLine::HR => self.blocks.push(MdBlock::HR),
+            PurifiedMdLine::EmptyLine => (),
+        }
+    }
+
+    ///
+    /// item text with its lazy continuation, and all the
+    /// indented lines after it which become its children.
+    ///
+    fn list_item(&self, task: Option<bool>, text: String, lines: &mut Lines) -> ListItem {
+        let mut item = vec![text];
+        while let Some(s) = next_lazy_text(lines) {
+            item.push(s);
+        }
+
+        // indented lines, can have blank lines in between
+        let mut children = vec![];
+        loop {
+            let blanks = lines.blanks_at(0);
+            match lines.peek_nth(blanks) {
+                Some(PurifiedMdLine::TabbedLine { .. }) => {
+                    children.extend(lines.lines.drain(..=blanks));
+                }
+                _ => break,
+            }
+        }
+        // children are only indented relative to the least indented one
+        let base = children
+            .iter()
+            .filter_map(|line| match line {
+                PurifiedMdLine::TabbedLine { level, .. } => Some(*level),
+                _ => None,
+            })
+            .min()
+            .unwrap_or(0);
+        let children = children
+            .into_iter()
+            .map(|line| match line {
+                PurifiedMdLine::TabbedLine { level, text } if level > base => {
+                    PurifiedMdLine::TabbedLine {
+                        level: level - base,
+                        text,
+                    }
+                }
+                PurifiedMdLine::TabbedLine { text, .. } => *text,
+                line => line,
+            })
+            .collect();
+
+        ListItem {
+            task,
+            data: MdString::from_string_with(join_lines(&item), &self.options),
+            children: MdBlockParser::parse(children, &self.options).blocks(),
+        }
+    }
+
+    /// line of a line block, with its indent as non-breaking spaces
+    fn line_block_line(&self, line: String) -> MdString {
+        let text = line.trim_start_matches(' ');
+        let indent = "\u{a0}".repeat(line.len() - text.len());
+        MdString::from_string_with(format!("{}{}", indent, text), &self.options)
+    }
+
+    /// text of `line` if it's all in one `*italic*`
+    fn italic_line(&self, line: &PurifiedMdLine) -> Option<MdString> {
+        let PurifiedMdLine::Text(s) = line else {
+            return None;
+        };
+        let mut inlines = MdString::from_string_with(s.trim().to_string(), &self.options)
+            .into_vec()
+            .into_iter();
+        match (inlines.next(), inlines.next()) {
+            (Some(MdInline::Italic(caption)), None) => Some(caption),
+            _ => None,
+        }
+    }
+
+    /// adds item to the list right before, or starts a new one
+    fn push_list_item(&mut self, ordered: bool, item: ListItem) {
+        match (ordered, self.blocks.last_mut()) {
+            (true, Some(MdBlock::OList { data })) | (false, Some(MdBlock::UList { data })) => {
+                data.push(item)
+            }
+            (true, _) => self.blocks.push(MdBlock::OList { data: vec![item] }),
+            (false, _) => self.blocks.push(MdBlock::UList { data: vec![item] }),
+        }
+    }
+
+    ///
+    /// terms and definitions until the list ends, blank lines can be
+    /// in between terms, definitions and the entries.
+    ///
+    fn definition_list(
+        &mut self,
+        mut terms: Vec<String>,
+        mut def_text: Option<String>,
+        lines: &mut Lines,
+    ) {
+        let mut data = vec![];
+        loop {
+            let mut defs = vec![];
+            loop {
+                if def_text.is_none() {
+                    let blanks = lines.blanks_at(0);
+                    if let Some(PurifiedMdLine::Definition { .. }) = lines.peek_nth(blanks) {
+                        lines.lines.drain(..blanks);
+                        if let Some(PurifiedMdLine::Definition { def_text: text }) = lines.next() {
+                            def_text = Some(text);
+                        }
+                    }
+                }
+                match def_text.take() {
+                    Some(text) => defs.push(self.definition(text, lines)),
+                    None => break,
+                }
+            }
+            data.push(DefinitionItem {
+                terms: terms
+                    .into_iter()
+                    .map(|term| MdString::from_string_with(term.trim().to_string(), &self.options))
+                    .collect(),
+                defs,
+            });
+
+            // next entry, after blank lines
+            let blanks = lines.blanks_at(0);
+            let term_count = term_lines_at(lines, blanks);
+            if term_count == 0 {
+                break;
+            }
+            lines.lines.drain(..blanks);
+            terms = lines
+                .by_ref()
+                .take(term_count)
+                .filter_map(|line| lazy_text(&line).cloned())
+                .collect();
+        }
+        self.blocks.push(MdBlock::Definition { data });
+    }
+
+    ///
+    /// blocks of a single definition, it can lazily continue or
+    /// have more paragraphs (or any other block) which are indented.
+    ///
+    fn definition(&self, def_text: String, lines: &mut Lines) -> Vec<MdBlock> {
+        let mut content = vec![self.reclassify(def_text)];
+        loop {
+            if lines.peek().is_some_and(|line| lazy_text(line).is_some() && !is_indented(line))
+                && term_lines_at(lines, 0) == 0
+            {
+                content.extend(lines.next());
+                continue;
+            }
+            let blanks = lines.blanks_at(0);
+            match lines.peek_nth(blanks) {
+                Some(line) if is_indented(line) => {
+                    content.extend(lines.lines.drain(..blanks));
+                    let line = lines.next().unwrap();
+                    content.push(self.deindent(line));
+                }
+                _ => break,
+            }
+        }
+        MdBlockParser::parse(content, &self.options).blocks()
+    }
+
+    /// re-reads a line which was only seen as a part of something else
+    fn reclassify(&self, line: String) -> PurifiedMdLine {
+        PurifiedMdLine::purify_with(to_mdline_with(line, &self.options), &self.options)
+    }
+
+    fn deindent(&self, line: PurifiedMdLine) -> PurifiedMdLine {
+        match line {
+            PurifiedMdLine::TabbedLine { text, .. } => *text,
+            PurifiedMdLine::Text(s) => self.reclassify(s.trim_start().to_string()),
+            line => line,
+        }
+    }
+}
+
+fn is_indented(line: &PurifiedMdLine) -> bool {
+    match line {
+        PurifiedMdLine::TabbedLine { .. } => true,
+        PurifiedMdLine::Text(s) => s.starts_with("  "),
+        _ => false,
+    }
+}
+
+///
+/// number of text lines starting at `from`, which are followed by
+/// a definition (maybe after blank lines), so they are terms.
+///
+fn term_lines_at(lines: &Lines, from: usize) -> usize {
+    let mut count = 0;
+    while lines
+        .peek_nth(from + count)
+        .is_some_and(|line| lazy_text(line).is_some() && !is_indented(line))
+    {
+        count += 1;
+    }
+    if count == 0 {
+        return 0;
+    }
+    let blanks = lines.blanks_at(from + count);
+    match lines.peek_nth(from + count + blanks) {
+        Some(PurifiedMdLine::Definition { .. }) => count,
+        _ => 0,
+    }
+}
+
+/// removes one level of quoting
+fn unquote(nest_level: u8, inside_md: PurifiedMdLine) -> PurifiedMdLine {
+    if nest_level > 1 {
+        PurifiedMdLine::Quote {
+            nest_level: nest_level - 1,
+            inside_md: Box::new(inside_md),
+        }
+    } else {
+        inside_md
+    }
+}
+
+///
+/// kind and title of an alert from the first line of a quote,
+/// `[!WARNING]` or `[!warning] Title`.
+///
+fn alert_marker(line: &str) -> Option<(AlertKind, Option<String>)> {
+    let rest = line.trim().strip_prefix("[!")?;
+    let end = rest.find(']')?;
+    let kind = AlertKind::from_name(&rest[..end])?;
+    let title = rest[end + 1..].trim();
+    Some((kind, (!title.is_empty()).then(|| title.to_string())))
+}
+
+/// is `line` (maybe quoted) a paragraph line
+fn is_text(line: &PurifiedMdLine) -> bool {
+    match line {
+        PurifiedMdLine::Text(_) | PurifiedMdLine::FailedText(_) => true,
+        PurifiedMdLine::Quote { inside_md, .. } => is_text(inside_md),
+        _ => false,
+    }
+}
+
+/// text of a line which can lazily continue a paragraph
+fn lazy_text(line: &PurifiedMdLine) -> Option<&String> {
+    match line {
+        PurifiedMdLine::Text(s) | PurifiedMdLine::FailedText(s) => Some(s),
+        PurifiedMdLine::TabbedLine { text, .. } => lazy_text(text),
+        _ => None,
+    }
+}
+
+fn next_lazy_text(lines: &mut Lines) -> Option<String> {
+    lines
+        .next_if(|line| lazy_text(line).is_some())
+        .and_then(|line| lazy_text(&line).cloned())
+}
+
+/// raw lines up to the closing fence, which is dropped
+fn fenced_lines(lines: &mut Lines) -> String {
+    let mut raw = vec![];
+    for line in lines.by_ref() {
+        match line {
+            PurifiedMdLine::CodeLine(s) => raw.push(s),
+            // closing fence
+            _ => break,
+        }
+    }
+    raw.join("\n")
+}
+
+///
+/// joins lines of a paragraph with `\n`, so that
+/// inline elements can span over multiple lines.
+///
+fn join_lines(lines: &[String]) -> String {
+    lines
+        .iter()
+        .map(|line| line.trim_start())
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// `|---|:---:|` kind of row, which seperates heading from data
+fn is_delimiter_row(row: &[String]) -> bool {
+    row.iter().all(|cell| {
+        let cell = cell.trim();
+        cell.contains('-') && cell.chars().all(|ch| ch == '-' || ch == ':')
+    })
+}
+
+fn table(rows: Vec<Vec<String>>, options: &ParserOptions) -> MdBlock {
+    let has_heading = rows.len() > 1 && is_delimiter_row(&rows[1]);
+    let data = rows
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| !(has_heading && *i == 1))
+        .map(|(i, row)| {
+            let kind = if has_heading && i == 0 {
+                TableRow::Heading
+            } else {
+                TableRow::Data
+            };
+            let cells = row
+                .into_iter()
+                .map(|cell| MdString::from_string_with(cell.trim().to_string(), options))
+                .collect();
+            (kind, cells)
+        })
+        .collect();
+    MdBlock::Table { data }
+}
+
+#[cfg(test)]
+mod block_testing {
+    use super::*;
+    use crate::md_inline_parser::md_inline::MdInline::*;
+    use crate::md_attributes::Attributes;
+
+    fn parse(data: &str) -> Vec<MdBlock> {
+        crate::parser_md(data.to_string()).blocks
+    }
+
+    fn text(s: &str) -> MdString {
+        MdString::from_vec(vec![InlineString(s.to_string())])
+    }
+
+    fn item(data: MdString) -> ListItem {
+        ListItem {
+            task: None,
+            data,
+            children: vec![],
+        }
+    }
+
+    #[test]
+    fn parah_joins_lines() {
+        assert_eq!(
+            parse("first line  \nsecond *line\nthird* line\n\nnext parah"),
+            vec![
+                MdBlock::Parah(MdString::from_vec(vec![
+                    InlineString("first line".to_string()),
+                    HardBreak,
+                    InlineString("second ".to_string()),
+                    Italic(MdString::from_vec(vec![
+                        InlineString("line".to_string()),
+                        SoftBreak,
+                        InlineString("third".to_string()),
+                    ])),
+                    InlineString(" line".to_string()),
+                ])),
+                MdBlock::Parah(text("next parah")),
+            ]
+        );
+    }
+
+    #[test]
+    fn lazy_continuation() {
+        assert_eq!(
+            parse("> quoted\nlazy\n- item\n  more"),
+            vec![
+                MdBlock::BlockQuote {
+                    data: vec![MdBlock::Parah(MdString::from_vec(vec![
+                        InlineString("quoted".to_string()),
+                        SoftBreak,
+                        InlineString("lazy".to_string()),
+                    ]))]
+                },
+                MdBlock::UList {
+                    data: vec![item(MdString::from_vec(vec![
+                        InlineString("item".to_string()),
+                        SoftBreak,
+                        InlineString("more".to_string()),
+                    ]))]
+                },
+            ]
+        );
+
+        // only paragraphs continue lazily
+        assert_eq!(
+            parse("> # head\nnot lazy"),
+            vec![
+                MdBlock::BlockQuote {
+                    data: vec![MdBlock::Head {
+                        level: 1,
+                        attrs: Attributes::default(),
+                        data: text("head")
+                    }]
+                },
+                MdBlock::Parah(text("not lazy")),
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_block_markers() {
+        assert_eq!(
+            parse("\\# not a heading\n\\- not a list \\a"),
+            vec![MdBlock::Parah(MdString::from_vec(vec![
+                InlineString("# not a heading".to_string()),
+                SoftBreak,
+                InlineString("- not a list \\a".to_string()),
+            ]))]
+        );
+    }
+
+    #[test]
+    fn commonmark_options() {
+        assert_eq!(
+            crate::parser_md_with_options(
+                "term\n: def\n- [ ] task ==a==".to_string(),
+                &ParserOptions::commonmark()
+            )
+            .blocks,
+            vec![
+                MdBlock::Parah(MdString::from_vec(vec![
+                    InlineString("term".to_string()),
+                    SoftBreak,
+                    InlineString(": def".to_string()),
+                ])),
+                MdBlock::UList {
+                    data: vec![item(MdString::from_vec(vec![
+                        LinkText(text(" ")),
+                        InlineString(" task ==a==".to_string()),
+                    ]))]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_task_list() {
+        assert_eq!(
+            parse("* [x] done\n  - [ ] sub\n\n    1. [X] subsub\n+ plain\n1) [ ] ordered"),
+            vec![
+                MdBlock::UList {
+                    data: vec![
+                        ListItem {
+                            task: Some(true),
+                            data: text("done"),
+                            children: vec![MdBlock::UList {
+                                data: vec![ListItem {
+                                    task: Some(false),
+                                    data: text("sub"),
+                                    children: vec![MdBlock::OList {
+                                        data: vec![ListItem {
+                                            task: Some(true),
+                                            data: text("subsub"),
+                                            children: vec![],
+                                        }]
+                                    }],
+                                }]
+                            }],
+                        },
+                        item(text("plain")),
+                    ]
+                },
+                MdBlock::OList {
+                    data: vec![ListItem {
+                        task: Some(false),
+                        data: text("ordered"),
+                        children: vec![],
+                    }]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn definition_list() {
+        assert_eq!(
+            parse("Apple\n: fruit\nlazily\n\nOrange\n\n: color\n\n\tmore\n\nafter"),
+            vec![
+                MdBlock::Definition {
+                    data: vec![
+                        DefinitionItem {
+                            terms: vec![text("Apple")],
+                            defs: vec![vec![MdBlock::Parah(MdString::from_vec(vec![
+                                InlineString("fruit".to_string()),
+                                SoftBreak,
+                                InlineString("lazily".to_string()),
+                            ]))]],
+                        },
+                        DefinitionItem {
+                            terms: vec![text("Orange")],
+                            defs: vec![vec![
+                                MdBlock::Parah(text("color")),
+                                MdBlock::Parah(text("more")),
+                            ]],
+                        },
+                    ]
+                },
+                MdBlock::Parah(text("after")),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_blocks() {
+        assert_eq!(
+            parse("```\n# code\n```\n| a | b |\n|---|---|\n| 1 | 2 |"),
+            vec![
+                MdBlock::CodeBlock {
+                    lang: None,
+                    attrs: Attributes::default(),
+                    code: "# code".to_string()
+                },
+                MdBlock::Table {
+                    data: vec![
+                        (TableRow::Heading, vec![text("a"), text("b")]),
+                        (TableRow::Data, vec![text("1"), text("2")]),
+                    ]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn line_blocks() {
+        assert_eq!(
+            parse("| The *rain*\n|    in Spain\n|\n| a | b |\n\n|no space"),
+            vec![
+                MdBlock::LineBlock(vec![
+                    MdString::from_vec(vec![
+                        InlineString("The ".to_string()),
+                        Italic(text("rain")),
+                    ]),
+                    text("\u{a0}\u{a0}\u{a0}in Spain"),
+                    MdString::new(),
+                ]),
+                MdBlock::Table {
+                    data: vec![(TableRow::Data, vec![text("a"), text("b")])]
+                },
+                MdBlock::Parah(text("|no space")),
+            ]
+        );
+    }
+
+    #[test]
+    fn alerts() {
+        assert_eq!(
+            parse("> [!Caution] Do *not*\n> run\n\n> [!NOPE]\n> quote"),
+            vec![
+                MdBlock::Alert {
+                    kind: AlertKind::Caution,
+                    title: Some(MdString::from_vec(vec![
+                        InlineString("Do ".to_string()),
+                        Italic(text("not")),
+                    ])),
+                    data: vec![MdBlock::Parah(text("run"))],
+                },
+                MdBlock::BlockQuote {
+                    data: vec![MdBlock::Parah(MdString::from_vec(vec![
+                        LinkText(text("!NOPE")),
+                        SoftBreak,
+                        InlineString("quote".to_string()),
+                    ]))],
+                },
+            ]
+        );
+        assert!(matches!(
+            parse("> [!NOTE]")[0],
+            MdBlock::Alert { kind: AlertKind::Note, title: None, .. }
+        ));
+        let blocks = crate::parser_md_with_options(
+            "> [!NOTE]".to_string(),
+            &ParserOptions::commonmark(),
+        )
+        .blocks;
+        assert!(matches!(blocks[0], MdBlock::BlockQuote { .. }));
+    }
+
+    #[test]
+    fn containers() {
+        let attrs = |data: &str| Attributes::parse(data);
+        assert_eq!(
+            parse(":::: columns {.wide}\n::: {#left}\nleft\n:::\n::: right\n\n> quoted\n:::\n::::\n::: open\ntext"),
+            vec![
+                MdBlock::Container {
+                    name: "columns".to_string(),
+                    attrs: attrs(".wide"),
+                    children: vec![
+                        MdBlock::Container {
+                            name: String::new(),
+                            attrs: attrs("#left"),
+                            children: vec![MdBlock::Parah(text("left"))],
+                        },
+                        MdBlock::Container {
+                            name: "right".to_string(),
+                            attrs: Attributes::default(),
+                            children: vec![MdBlock::BlockQuote {
+                                data: vec![MdBlock::Parah(text("quoted"))]
+                            }],
+                        },
+                    ],
+                },
+                // closed by the end
+                MdBlock::Container {
+                    name: "open".to_string(),
+                    attrs: Attributes::default(),
+                    children: vec![MdBlock::Parah(text("text"))],
+                },
+            ]
+        );
     }
 }
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use crate::{
+    md_attributes::Attributes,
+    md_block::{DefinitionItem, ListItem, MdBlock},
+};
+
+type Handler = Box<dyn Fn(&Attributes, Vec<MdBlock>) -> Vec<MdBlock>>;
+
+///
+/// handlers which turn `Container`s into other blocks, by name.
+///
+/// containers without a handler are kept as they are, inner
+/// containers are handled before the ones they are in.
+///
+#[derive(Default)]
+pub struct ContainerRegistry {
+    handlers: HashMap<String, Handler>,
+}
+
+impl ContainerRegistry {
+    pub fn new() -> Self {
+        ContainerRegistry {
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// `handler` gets the attributes and children of every `name` container
+    pub fn register<F>(&mut self, name: &str, handler: F)
+    where
+        F: Fn(&Attributes, Vec<MdBlock>) -> Vec<MdBlock> + 'static,
+    {
+        self.handlers.insert(name.to_string(), Box::new(handler));
+    }
+
+    /// replaces the containers anywhere in `blocks`
+    pub fn apply(&self, blocks: Vec<MdBlock>) -> Vec<MdBlock> {
+        let mut out = vec![];
+        for block in blocks {
+            match block {
+                MdBlock::Container {
+                    name,
+                    attrs,
+                    children,
+                } => {
+                    let children = self.apply(children);
+                    match self.handlers.get(&name) {
+                        Some(handler) => out.extend(handler(&attrs, children)),
+                        None => out.push(MdBlock::Container {
+                            name,
+                            attrs,
+                            children,
+                        }),
+                    }
+                }
+                MdBlock::BlockQuote { data } => out.push(MdBlock::BlockQuote {
+                    data: self.apply(data),
+                }),
+                MdBlock::Alert { kind, title, data } => out.push(MdBlock::Alert {
+                    kind,
+                    title,
+                    data: self.apply(data),
+                }),
+                MdBlock::OList { data } => out.push(MdBlock::OList {
+                    data: self.apply_items(data),
+                }),
+                MdBlock::UList { data } => out.push(MdBlock::UList {
+                    data: self.apply_items(data),
+                }),
+                MdBlock::Definition { data } => out.push(MdBlock::Definition {
+                    data: data
+                        .into_iter()
+                        .map(|item| DefinitionItem {
+                            terms: item.terms,
+                            defs: item.defs.into_iter().map(|def| self.apply(def)).collect(),
+                        })
+                        .collect(),
+                }),
+                block => out.push(block),
+            }
+        }
+        out
+    }
+
+    fn apply_items(&self, items: Vec<ListItem>) -> Vec<ListItem> {
+        items
+            .into_iter()
+            .map(|item| ListItem {
+                children: self.apply(item.children),
+                ..item
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod container_testing {
+    use super::*;
+    use crate::{md_inline_parser::md_string::MdString, parser_md};
+
+    #[test]
+    fn handle_by_name() {
+        let mut registry = ContainerRegistry::new();
+        // a `note` becomes a quote, with its title as the first paragraph
+        registry.register("note", |attrs, mut children| {
+            if let Some(title) = attrs.args.first() {
+                children.insert(0, MdBlock::Parah(MdString::from_string(title.clone())));
+            }
+            vec![MdBlock::BlockQuote { data: children }]
+        });
+
+        let blocks = parser_md("> ::: note \"Hi\"\n> body\n> :::\n::: other\n:::".to_string()).blocks;
+        assert_eq!(
+            registry.apply(blocks),
+            vec![
+                MdBlock::BlockQuote {
+                    data: vec![MdBlock::BlockQuote {
+                        data: vec![
+                            MdBlock::Parah(MdString::from_string("Hi".to_string())),
+                            MdBlock::Parah(MdString::from_string("body".to_string())),
+                        ]
+                    }]
+                },
+                MdBlock::Container {
+                    name: "other".to_string(),
+                    attrs: Attributes::default(),
+                    children: vec![],
+                },
+            ]
+        );
+    }
+}
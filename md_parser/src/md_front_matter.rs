@@ -0,0 +1,599 @@
+//!
+//! Front matter is only looked for at the very start of the document,
+//! `---` fenced YAML or `+++` fenced TOML.
+//!
+//! Only the commonly used parts of both are understood, anything
+//! else is kept as a string. `raw` always has the whole of it.
+//!
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrontMatterKind {
+    Yaml,
+    Toml,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontMatterValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    List(Vec<FrontMatterValue>),
+    /// keys are in the order they were written
+    Map(Vec<(String, FrontMatterValue)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontMatter {
+    pub kind: FrontMatterKind,
+    /// text in between the fences
+    pub raw: String,
+    pub data: FrontMatterValue,
+}
+
+impl FrontMatterValue {
+    /// value of `key` if this is a map
+    pub fn get(&self, key: &str) -> Option<&FrontMatterValue> {
+        match self {
+            FrontMatterValue::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FrontMatterValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+///
+/// finds front matter at the start of `data`, returns it with
+/// the number of lines it takes (fences included).
+///
+pub(crate) fn extract(data: &str) -> Option<(FrontMatter, usize)> {
+    let mut lines = data.lines();
+    let (kind, closing): (FrontMatterKind, &[&str]) = match lines.next()?.trim_end() {
+        "---" => (FrontMatterKind::Yaml, &["---", "..."]),
+        "+++" => (FrontMatterKind::Toml, &["+++"]),
+        _ => return None,
+    };
+
+    let mut raw = vec![];
+    for line in lines {
+        if closing.contains(&line.trim_end()) {
+            // with both the fences
+            let line_count = raw.len() + 2;
+            let raw = raw.join("\n");
+            let data = match kind {
+                FrontMatterKind::Yaml => parse_yaml(&raw),
+                FrontMatterKind::Toml => parse_toml(&raw),
+            };
+            return Some((FrontMatter { kind, raw, data }, line_count));
+        }
+        raw.push(line);
+    }
+    // never closed, so it's not front matter
+    None
+}
+
+// YAML
+
+fn parse_yaml(raw: &str) -> FrontMatterValue {
+    // (indent, text) of lines which have something
+    let mut lines: Vec<(usize, String)> = raw
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#')
+        })
+        .map(|line| {
+            let text = line.trim_start();
+            (line.len() - text.len(), text.trim_end().to_string())
+        })
+        .collect();
+    let mut pos = 0;
+    match lines.first() {
+        Some(&(indent, _)) => yaml_block(&mut lines, &mut pos, indent),
+        None => FrontMatterValue::Map(vec![]),
+    }
+}
+
+/// a mapping or a list, whose lines are all at `indent`
+fn yaml_block(lines: &mut Vec<(usize, String)>, pos: &mut usize, indent: usize) -> FrontMatterValue {
+    let is_list = lines
+        .get(*pos)
+        .is_some_and(|(_, text)| text == "-" || text.starts_with("- "));
+
+    if is_list {
+        let mut items = vec![];
+        while let Some((line_indent, text)) = lines.get(*pos).cloned() {
+            if line_indent != indent || !(text == "-" || text.starts_with("- ")) {
+                break;
+            }
+            let rest = text[1..].trim_start().to_string();
+            if rest.is_empty() {
+                *pos += 1;
+                // a `-` at the same indent is the next item, not a list in this one
+                let deeper = lines.get(*pos).is_some_and(|(child, _)| *child > indent);
+                items.push(if deeper {
+                    yaml_child(lines, pos, indent)
+                } else {
+                    FrontMatterValue::Null
+                });
+            } else if yaml_key_value(&rest).is_some() {
+                // `- key: value` starts a mapping inside the list,
+                // read it as if it was on its own line.
+                let item_indent = indent + (text.len() - rest.len());
+                lines[*pos] = (item_indent, rest);
+                items.push(yaml_block(lines, pos, item_indent));
+            } else {
+                *pos += 1;
+                items.push(yaml_scalar(&rest));
+            }
+        }
+        return FrontMatterValue::List(items);
+    }
+
+    let mut entries = vec![];
+    while let Some((line_indent, text)) = lines.get(*pos).cloned() {
+        if line_indent != indent {
+            break;
+        }
+        *pos += 1;
+        let Some((key, value)) = yaml_key_value(&text) else {
+            // not a `key: value`, can't do anything with it
+            continue;
+        };
+        let value = match value {
+            "" => yaml_child(lines, pos, indent),
+            "|" | "|-" | ">" | ">-" => yaml_block_scalar(lines, pos, indent, value),
+            value => yaml_scalar(value),
+        };
+        entries.push((key, value));
+    }
+    FrontMatterValue::Map(entries)
+}
+
+/// value of a `key:` or `-` with nothing after it
+fn yaml_child(lines: &mut Vec<(usize, String)>, pos: &mut usize, indent: usize) -> FrontMatterValue {
+    match lines.get(*pos) {
+        Some((child_indent, _)) if *child_indent > indent => {
+            let child_indent = *child_indent;
+            yaml_block(lines, pos, child_indent)
+        }
+        // list of a key can be at the same indent as the key
+        Some((child_indent, text)) if *child_indent == indent && text.starts_with("- ") => {
+            yaml_block(lines, pos, indent)
+        }
+        _ => FrontMatterValue::Null,
+    }
+}
+
+/// `|` keeps the line endings and `>` folds them into spaces
+fn yaml_block_scalar(
+    lines: &mut [(usize, String)],
+    pos: &mut usize,
+    indent: usize,
+    style: &str,
+) -> FrontMatterValue {
+    let start = *pos;
+    while lines.get(*pos).is_some_and(|(line_indent, _)| *line_indent > indent) {
+        *pos += 1;
+    }
+    // only the indent all of the lines have is taken out
+    let block = &lines[start..*pos];
+    let common = block.iter().map(|(line_indent, _)| *line_indent).min().unwrap_or(0);
+    let text: Vec<String> = block
+        .iter()
+        .map(|(line_indent, line)| format!("{}{}", " ".repeat(line_indent - common), line))
+        .collect();
+    let mut value = text.join(if style.starts_with('|') { "\n" } else { " " });
+    if !style.ends_with('-') {
+        value.push('\n');
+    }
+    FrontMatterValue::String(value)
+}
+
+fn yaml_key_value(text: &str) -> Option<(String, &str)> {
+    let (key, value) = if let Some(key) = text.strip_suffix(':') {
+        (key, "")
+    } else {
+        let (key, value) = text.split_once(": ")?;
+        (key, value.trim())
+    };
+    let key = key.trim();
+    if key.is_empty() || key.starts_with(['"', '\'']) && !key.ends_with(['"', '\'']) {
+        return None;
+    }
+    Some((unquote(key).to_string(), strip_comment(value)))
+}
+
+fn yaml_scalar(text: &str) -> FrontMatterValue {
+    let text = strip_comment(text);
+    if let Some(items) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return FrontMatterValue::List(
+            split_flow(items)
+                .into_iter()
+                .map(yaml_scalar)
+                .collect(),
+        );
+    }
+    if let Some(entries) = text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+        return FrontMatterValue::Map(
+            split_flow(entries)
+                .into_iter()
+                .filter_map(|entry| {
+                    let (key, value) = entry.split_once(':')?;
+                    Some((unquote(key.trim()).to_string(), yaml_scalar(value.trim())))
+                })
+                .collect(),
+        );
+    }
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => return FrontMatterValue::Null,
+        "true" | "True" | "TRUE" => return FrontMatterValue::Bool(true),
+        "false" | "False" | "FALSE" => return FrontMatterValue::Bool(false),
+        _ => (),
+    }
+    if text.starts_with(['"', '\'']) {
+        return FrontMatterValue::String(unquote(text).to_string());
+    }
+    number(text).unwrap_or_else(|| FrontMatterValue::String(text.to_string()))
+}
+
+// TOML
+
+fn parse_toml(raw: &str) -> FrontMatterValue {
+    let mut root = FrontMatterValue::Map(vec![]);
+    // keys of the table we are in
+    let mut table: Vec<String> = vec![];
+
+    let mut lines = raw.lines();
+    while let Some(line) = lines.next() {
+        let line = strip_comment(line.trim());
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+            // array of tables, a new table is added every time
+            table = dotted_keys(name);
+            let (last, parents) = table.split_last().unwrap();
+            let parent = toml_table(&mut root, parents);
+            match map_entry(parent, last) {
+                FrontMatterValue::List(items) => items.push(FrontMatterValue::Map(vec![])),
+                value => *value = FrontMatterValue::List(vec![FrontMatterValue::Map(vec![])]),
+            }
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            table = dotted_keys(name);
+            toml_table(&mut root, &table);
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        // arrays can go over multiple lines
+        let mut value = value.trim().to_string();
+        while value.starts_with('[') && !brackets_closed(&value) {
+            match lines.next() {
+                Some(next) => {
+                    value.push(' ');
+                    value.push_str(strip_comment(next.trim()));
+                }
+                None => break,
+            }
+        }
+
+        let mut keys = dotted_keys(key);
+        let last = keys.pop().unwrap();
+        let mut path = table.clone();
+        path.extend(keys);
+        let parent = toml_table(&mut root, &path);
+        *map_entry(parent, &last) = toml_value(&value);
+    }
+    root
+}
+
+/// table at `path`, made if not there yet
+fn toml_table<'a>(root: &'a mut FrontMatterValue, path: &[String]) -> &'a mut FrontMatterValue {
+    let mut table = root;
+    for key in path {
+        let entry = map_entry(table, key);
+        if !matches!(entry, FrontMatterValue::Map(_) | FrontMatterValue::List(_)) {
+            *entry = FrontMatterValue::Map(vec![]);
+        }
+        // a key of an array of tables goes to its last table
+        table = match entry {
+            FrontMatterValue::List(items) => {
+                if !matches!(items.last(), Some(FrontMatterValue::Map(_))) {
+                    items.push(FrontMatterValue::Map(vec![]));
+                }
+                items.last_mut().unwrap()
+            }
+            entry => entry,
+        };
+    }
+    table
+}
+
+/// value of `key` in `map`, `Null` is added when not there
+fn map_entry<'a>(map: &'a mut FrontMatterValue, key: &str) -> &'a mut FrontMatterValue {
+    if !matches!(map, FrontMatterValue::Map(_)) {
+        *map = FrontMatterValue::Map(vec![]);
+    }
+    let FrontMatterValue::Map(entries) = map else {
+        unreachable!()
+    };
+    let pos = match entries.iter().position(|(k, _)| k == key) {
+        Some(pos) => pos,
+        None => {
+            entries.push((key.to_string(), FrontMatterValue::Null));
+            entries.len() - 1
+        }
+    };
+    &mut entries[pos].1
+}
+
+fn dotted_keys(keys: &str) -> Vec<String> {
+    keys.split('.')
+        .map(|key| unquote(key.trim()).to_string())
+        .collect()
+}
+
+fn toml_value(text: &str) -> FrontMatterValue {
+    let text = text.trim();
+    if let Some(items) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return FrontMatterValue::List(
+            split_flow(items)
+                .into_iter()
+                .map(toml_value)
+                .collect(),
+        );
+    }
+    if let Some(entries) = text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+        let mut table = FrontMatterValue::Map(vec![]);
+        for entry in split_flow(entries) {
+            if let Some((key, value)) = entry.split_once('=') {
+                *map_entry(&mut table, unquote(key.trim())) = toml_value(value);
+            }
+        }
+        return table;
+    }
+    match text {
+        "true" => return FrontMatterValue::Bool(true),
+        "false" => return FrontMatterValue::Bool(false),
+        _ => (),
+    }
+    if text.starts_with(['"', '\'']) {
+        return FrontMatterValue::String(unquote(text).to_string());
+    }
+    // dates and everything else stay as they are
+    number(&text.replace('_', "")).unwrap_or_else(|| FrontMatterValue::String(text.to_string()))
+}
+
+// shared by both
+
+fn number(text: &str) -> Option<FrontMatterValue> {
+    if let Ok(int) = text.parse::<i64>() {
+        return Some(FrontMatterValue::Integer(int));
+    }
+    // `inf` and `nan` are parsed by rust too, only take digits
+    if text.starts_with(|ch: char| ch.is_ascii_digit() || ch == '-' || ch == '+' || ch == '.') {
+        if let Ok(float) = text.parse::<f64>() {
+            return Some(FrontMatterValue::Float(float));
+        }
+    }
+    None
+}
+
+fn unquote(text: &str) -> &str {
+    for quote in ['"', '\''] {
+        if text.len() > 1 && text.starts_with(quote) && text.ends_with(quote) {
+            return &text[1..text.len() - 1];
+        }
+    }
+    text
+}
+
+///
+/// removes `# comment` which isn't inside quotes. a quote only starts
+/// a string at the start of a value, so the `'` of `it's` is just text.
+///
+fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+    // last char which isn't a space
+    let mut last = None;
+    for (i, ch) in text.char_indices() {
+        let starts_value = matches!(last, None | Some('[' | '{' | ',' | ':' | '=' | '-'));
+        if !ch.is_whitespace() {
+            last = Some(ch);
+        }
+        match (quote, ch) {
+            (None, '"' | '\'') if starts_value => quote = Some(ch),
+            (Some(q), ch) if q == ch => quote = None,
+            (None, '#') if i == 0 || text[..i].ends_with([' ', '\t']) => {
+                return text[..i].trim_end();
+            }
+            _ => (),
+        }
+    }
+    text
+}
+
+fn brackets_closed(text: &str) -> bool {
+    let mut depth = 0;
+    let mut quote = None;
+    for ch in text.chars() {
+        match (quote, ch) {
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(q), ch) if q == ch => quote = None,
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            _ => (),
+        }
+    }
+    depth <= 0
+}
+
+/// splits `a, [b, c], "d, e"` on the commas which are at the top
+fn split_flow(text: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, ch) in text.char_indices() {
+        match (quote, ch) {
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(q), ch) if q == ch => quote = None,
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                items.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    items.push(text[start..].trim());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+#[cfg(test)]
+mod front_matter_testing {
+    use super::*;
+    use FrontMatterValue::*;
+
+    fn s(text: &str) -> FrontMatterValue {
+        String(text.to_string())
+    }
+
+    #[test]
+    fn yaml_front_matter() {
+        let data = "---\n\
+                    title: \"Hello: World\" # comment\n\
+                    draft: false\n\
+                    weight: 3\n\
+                    tags: [a, 'b, c']\n\
+                    authors:\n\
+                    - name: me\n  \
+                      mail: me@x.y\n\
+                    - you\n\
+                    nested:\n  \
+                      deeper:\n    \
+                        ratio: 2.5\n\
+                    summary: |\n  \
+                      line one\n    \
+                        indented\n  \
+                      line two\n\
+                    ---\n\
+                    # body";
+        let (front_matter, lines) = extract(data).unwrap();
+        assert_eq!(lines, 17);
+        assert_eq!(front_matter.kind, FrontMatterKind::Yaml);
+        assert_eq!(
+            front_matter.data,
+            Map(vec![
+                ("title".to_string(), s("Hello: World")),
+                ("draft".to_string(), Bool(false)),
+                ("weight".to_string(), Integer(3)),
+                ("tags".to_string(), List(vec![s("a"), s("b, c")])),
+                (
+                    "authors".to_string(),
+                    List(vec![
+                        Map(vec![
+                            ("name".to_string(), s("me")),
+                            ("mail".to_string(), s("me@x.y")),
+                        ]),
+                        s("you"),
+                    ])
+                ),
+                (
+                    "nested".to_string(),
+                    Map(vec![(
+                        "deeper".to_string(),
+                        Map(vec![("ratio".to_string(), Float(2.5))])
+                    )])
+                ),
+                ("summary".to_string(), s("line one\n  indented\nline two\n")),
+            ])
+        );
+    }
+
+    #[test]
+    fn yaml_empty_items_and_apostrophes() {
+        let data = "---\n\
+                    items:\n\
+                    - a\n\
+                    -\n\
+                    - c\n\
+                    title: it's here # note\n\
+                    quoted: 'a # b' # note\n\
+                    ---\n";
+        let (front_matter, _) = extract(data).unwrap();
+        assert_eq!(
+            front_matter.data,
+            Map(vec![
+                ("items".to_string(), List(vec![s("a"), Null, s("c")])),
+                ("title".to_string(), s("it's here")),
+                ("quoted".to_string(), s("a # b")),
+            ])
+        );
+    }
+
+    #[test]
+    fn toml_front_matter() {
+        let data = "+++\n\
+                    title = 'Hello'\n\
+                    count = 1_000\n\
+                    date = 2024-01-02\n\
+                    list = [\n  1,\n  2,\n]\n\
+                    [params]\n\
+                    math = true\n\
+                    site.name = \"x\"\n\
+                    [[menu]]\n\
+                    name = \"a\"\n\
+                    [[menu]]\n\
+                    name = \"b\"\n\
+                    +++\n";
+        let (front_matter, lines) = extract(data).unwrap();
+        assert_eq!(lines, 16);
+        assert_eq!(front_matter.kind, FrontMatterKind::Toml);
+        assert_eq!(front_matter.data.get("title"), Some(&s("Hello")));
+        assert_eq!(front_matter.data.get("count"), Some(&Integer(1000)));
+        assert_eq!(front_matter.data.get("date"), Some(&s("2024-01-02")));
+        assert_eq!(
+            front_matter.data.get("list"),
+            Some(&List(vec![Integer(1), Integer(2)]))
+        );
+        let params = front_matter.data.get("params").unwrap();
+        assert_eq!(params.get("math"), Some(&Bool(true)));
+        assert_eq!(
+            params.get("site"),
+            Some(&Map(vec![("name".to_string(), s("x"))]))
+        );
+        assert_eq!(
+            front_matter.data.get("menu"),
+            Some(&List(vec![
+                Map(vec![("name".to_string(), s("a"))]),
+                Map(vec![("name".to_string(), s("b"))]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn only_at_start_and_closed() {
+        assert_eq!(extract("text\n---\na: 1\n---\n"), None);
+        assert_eq!(extract("---\na: 1\n"), None);
+        assert_eq!(extract("---\n---\n").unwrap().1, 2);
+    }
+}
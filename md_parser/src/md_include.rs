@@ -0,0 +1,277 @@
+use crate::{
+    md_attributes::Attributes,
+    md_block::{DefinitionItem, ListItem, MdBlock},
+    md_options::ParserOptions,
+    md_renderer::html_renderer::plain_text,
+    parser_md_with_options,
+};
+
+///
+/// reads the files of `{{#include path}}`, `path` is already
+/// made relative to the including file, like `parts/intro.md`.
+///
+/// `None` means there is no such file, and the include is left
+/// as an `MdBlock::Include`.
+///
+pub trait IncludeResolver {
+    fn read(&self, path: &str) -> Option<String>;
+}
+
+impl<F> IncludeResolver for F
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn read(&self, path: &str) -> Option<String> {
+        self(path)
+    }
+}
+
+///
+/// reads files from the disk, relative to the working directory.
+///
+pub struct FileIncludeResolver;
+
+impl IncludeResolver for FileIncludeResolver {
+    fn read(&self, path: &str) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+}
+
+///
+/// replaces every `MdBlock::Include` in `blocks` with the blocks
+/// of its file, includes in there are spliced in too.
+///
+/// `stack` has the files being included right now, the last one is
+/// the file `blocks` come from. including any of them again would
+/// never end, so such an include is left as it is.
+///
+pub(crate) fn expand(
+    blocks: &mut Vec<MdBlock>,
+    stack: &mut Vec<String>,
+    options: &ParserOptions,
+    resolver: &dyn IncludeResolver,
+) {
+    let mut out = vec![];
+    for mut block in std::mem::take(blocks) {
+        match &mut block {
+            MdBlock::Include { path, attrs } => {
+                if let Some(included) = include(path, attrs, stack, options, resolver) {
+                    out.extend(included);
+                    continue;
+                }
+            }
+            MdBlock::BlockQuote { data }
+            | MdBlock::Alert { data, .. }
+            | MdBlock::Container { children: data, .. } => {
+                expand(data, stack, options, resolver)
+            }
+            MdBlock::OList { data } | MdBlock::UList { data } => {
+                for ListItem { children, .. } in data {
+                    expand(children, stack, options, resolver);
+                }
+            }
+            MdBlock::Definition { data } => {
+                for def in data.iter_mut().flat_map(|item| &mut item.defs) {
+                    expand(def, stack, options, resolver);
+                }
+            }
+            _ => (),
+        }
+        out.push(block);
+    }
+    *blocks = out;
+}
+
+/// blocks of the included file, `None` if it can't be included
+fn include(
+    path: &str,
+    attrs: &Attributes,
+    stack: &mut Vec<String>,
+    options: &ParserOptions,
+    resolver: &dyn IncludeResolver,
+) -> Option<Vec<MdBlock>> {
+    let including = stack.last().map_or("", String::as_str);
+    let path = join_path(including, path);
+    if stack.contains(&path) {
+        return None;
+    }
+    let mut text = resolver.read(&path)?;
+    if let Some(lines) = attrs.get("lines") {
+        text = select_lines(&text, lines)?;
+    }
+    let mut blocks = parser_md_with_options(text, options).blocks;
+    stack.push(path);
+    expand(&mut blocks, stack, options, resolver);
+    stack.pop();
+    if let Some(section) = attrs.get("section") {
+        blocks = select_section(blocks, section)?;
+    }
+    if let Some(shift) = attrs.get("shift") {
+        shift_headings(&mut blocks, shift.parse().ok()?);
+    }
+    Some(blocks)
+}
+
+///
+/// `path` relative to the directory of `including`, with `.` and
+/// `..` taken out. a path starting with `/` is kept as it is.
+///
+fn join_path(including: &str, path: &str) -> String {
+    if path.starts_with('/') {
+        return path.to_string();
+    }
+    let dir = including.rfind('/').map_or("", |end| &including[..end]);
+    let mut parts: Vec<&str> = vec![];
+    for part in dir.split('/').chain(path.split('/')) {
+        match part {
+            "" | "." => (),
+            ".." if parts.last().is_some_and(|last| *last != "..") => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    let joined = parts.join("/");
+    if including.starts_with('/') {
+        format!("/{}", joined)
+    } else {
+        joined
+    }
+}
+
+///
+/// lines of `text` in `range`, counting from 1. `3-10`, `3-`
+/// till the end, `-10` from the start, or a single line `3`.
+///
+fn select_lines(text: &str, range: &str) -> Option<String> {
+    let (start, end) = match range.split_once('-') {
+        Some((start, end)) => (start.trim(), end.trim()),
+        None => (range.trim(), range.trim()),
+    };
+    let start: usize = if start.is_empty() { 1 } else { start.parse().ok()? };
+    let end: usize = if end.is_empty() { usize::MAX } else { end.parse().ok()? };
+    if start == 0 || start > end {
+        return None;
+    }
+    let lines = text
+        .lines()
+        .skip(start - 1)
+        .take(end - start + 1)
+        .collect::<Vec<_>>();
+    Some(lines.join("\n"))
+}
+
+///
+/// heading with the title or `#id` `section`, and the blocks
+/// under it until a heading of the same or an upper level.
+///
+fn select_section(blocks: Vec<MdBlock>, section: &str) -> Option<Vec<MdBlock>> {
+    let is_section = |block: &MdBlock| match block {
+        MdBlock::Head { attrs, data, .. } => {
+            attrs.id.as_deref() == Some(section) || plain_text(data).trim() == section
+        }
+        _ => false,
+    };
+    let start = blocks.iter().position(is_section)?;
+    let Some(MdBlock::Head { level, .. }) = blocks.get(start) else {
+        return None;
+    };
+    let level = *level;
+    let section = blocks
+        .into_iter()
+        .skip(start)
+        .enumerate()
+        .take_while(|(i, block)| {
+            *i == 0 || !matches!(block, MdBlock::Head { level: other, .. } if *other <= level)
+        })
+        .map(|(_, block)| block)
+        .collect();
+    Some(section)
+}
+
+/// moves headings `shift` levels down, or up when it's negative, keeping them in 1..=6
+fn shift_headings(blocks: &mut [MdBlock], shift: i32) {
+    for block in blocks {
+        match block {
+            MdBlock::Head { level, .. } => *level = (*level as i32 + shift).clamp(1, 6) as u8,
+            MdBlock::BlockQuote { data }
+            | MdBlock::Alert { data, .. }
+            | MdBlock::Container { children: data, .. } => shift_headings(data, shift),
+            MdBlock::OList { data } | MdBlock::UList { data } => {
+                for ListItem { children, .. } in data {
+                    shift_headings(children, shift);
+                }
+            }
+            MdBlock::Definition { data } => {
+                for DefinitionItem { defs, .. } in data {
+                    for def in defs {
+                        shift_headings(def, shift);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod include_testing {
+    use super::join_path;
+    use crate::{md_to_html, parser_md_with_includes, HtmlRenderer, ParserOptions};
+
+    fn files(path: &str) -> Option<String> {
+        let text = match path {
+            "book/main.md" => "# Book\n\n{{#include parts/intro.md shift=1}}\n\nEnd",
+            "book/parts/intro.md" => {
+                "# Intro\n\nHello\n\n{{#include ../shared/note.md section=\"Note\"}}"
+            }
+            "book/shared/note.md" => "# Other\n\nno\n\n# Note\n\nnoted\n\n## More\n\n# Last",
+            "book/loop.md" => "a\n\n{{#include loop.md}}",
+            "book/lines.md" => "one\n\ntwo\n\nthree",
+            _ => return None,
+        };
+        Some(text.to_string())
+    }
+
+    fn include_html(data: &str) -> String {
+        let options = ParserOptions::default();
+        let document = parser_md_with_includes(data.to_string(), "book/main.md", &options, &files);
+        HtmlRenderer::new().render(&document.blocks)
+    }
+
+    #[test]
+    fn spliced_in() {
+        assert_eq!(
+            include_html(&files("book/main.md").unwrap()),
+            "<h1>Book</h1>\n<h2>Intro</h2>\n<p>Hello</p>\n\
+             <h2>Note</h2>\n<p>noted</p>\n<h3>More</h3>\n<p>End</p>\n"
+        );
+        assert_eq!(
+            include_html("::: box\n{{#include lines.md lines=3-5}}\n:::"),
+            "<div class=\"box\">\n<p>two</p>\n<p>three</p>\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn left_out() {
+        // a cycle, a missing file and a missing section stay as they are
+        assert_eq!(include_html("{{#include loop.md}}"), "<p>a</p>\n");
+        assert_eq!(
+            include_html("{{#include nothing.md}}\n\n{{#include lines.md section=x}}"),
+            ""
+        );
+        assert_eq!(
+            md_to_html("{{#include a.md}}\n\n```\n{{#include a.md}}\n```".to_string()),
+            "<pre><code>{{#include a.md}}\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(join_path("book/main.md", "parts/a.md"), "book/parts/a.md");
+        assert_eq!(join_path("book/parts/a.md", "../b.md"), "book/b.md");
+        assert_eq!(join_path("main.md", "./a/../b.md"), "b.md");
+        assert_eq!(join_path("/docs/main.md", "../x.md"), "/x.md");
+        assert_eq!(join_path("main.md", "../x.md"), "../x.md");
+    }
+}
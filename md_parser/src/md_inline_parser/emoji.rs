@@ -0,0 +1,999 @@
+///
+/// emoji of a `:shortcode:`, `shortcode` is without the colons.
+///
+/// the names are GitHub's, but only the commonly used part of its
+/// list is here, any other shortcode is left as text.
+///
+pub(crate) fn emoji(shortcode: &str) -> Option<&'static str> {
+    EMOJI
+        .binary_search_by(|(name, _)| name.cmp(&shortcode))
+        .ok()
+        .map(|i| EMOJI[i].1)
+}
+
+#[test]
+fn test_emoji() {
+    assert_eq!(emoji("rocket"), Some("\u{1f680}"));
+    assert_eq!(emoji("+1"), emoji("thumbsup"));
+    assert_eq!(emoji("heart"), Some("\u{2764}\u{fe0f}"));
+    assert_eq!(emoji("not_an_emoji"), None);
+    assert!(EMOJI.windows(2).all(|pair| pair[0].0 < pair[1].0));
+}
+
+///
+/// shortcodes and their emoji, sorted by shortcode
+/// so it can be binary searched.
+///
+pub(crate) static EMOJI: [(&str, &str); 971] = [
+    ("+1", "\u{1f44d}"),
+    ("-1", "\u{1f44e}"),
+    ("100", "\u{1f4af}"),
+    ("1234", "\u{1f522}"),
+    ("1st_place_medal", "\u{1f947}"),
+    ("2nd_place_medal", "\u{1f948}"),
+    ("3rd_place_medal", "\u{1f949}"),
+    ("8ball", "\u{1f3b1}"),
+    ("a", "\u{1f170}\u{fe0f}"),
+    ("ab", "\u{1f18e}"),
+    ("abacus", "\u{1f9ee}"),
+    ("abc", "\u{1f524}"),
+    ("abcd", "\u{1f521}"),
+    ("adult", "\u{1f9d1}"),
+    ("airplane", "\u{2708}\u{fe0f}"),
+    ("alarm_clock", "\u{23f0}"),
+    ("alembic", "\u{2697}\u{fe0f}"),
+    ("alien", "\u{1f47d}"),
+    ("ambulance", "\u{1f691}"),
+    ("anchor", "\u{2693}"),
+    ("anger", "\u{1f4a2}"),
+    ("angry", "\u{1f620}"),
+    ("anguished", "\u{1f627}"),
+    ("ant", "\u{1f41c}"),
+    ("apple", "\u{1f34e}"),
+    ("aquarius", "\u{2652}"),
+    ("aries", "\u{2648}"),
+    ("arrow_backward", "\u{25c0}\u{fe0f}"),
+    ("arrow_down", "\u{2b07}\u{fe0f}"),
+    ("arrow_down_small", "\u{1f53d}"),
+    ("arrow_forward", "\u{25b6}\u{fe0f}"),
+    ("arrow_heading_down", "\u{2935}\u{fe0f}"),
+    ("arrow_heading_up", "\u{2934}\u{fe0f}"),
+    ("arrow_left", "\u{2b05}\u{fe0f}"),
+    ("arrow_lower_left", "\u{2199}\u{fe0f}"),
+    ("arrow_lower_right", "\u{2198}\u{fe0f}"),
+    ("arrow_right", "\u{27a1}\u{fe0f}"),
+    ("arrow_right_hook", "\u{21aa}\u{fe0f}"),
+    ("arrow_up", "\u{2b06}\u{fe0f}"),
+    ("arrow_up_down", "\u{2195}\u{fe0f}"),
+    ("arrow_up_small", "\u{1f53c}"),
+    ("arrow_upper_left", "\u{2196}\u{fe0f}"),
+    ("arrow_upper_right", "\u{2197}\u{fe0f}"),
+    ("arrows_clockwise", "\u{1f503}"),
+    ("arrows_counterclockwise", "\u{1f504}"),
+    ("art", "\u{1f3a8}"),
+    ("artificial_satellite", "\u{1f6f0}\u{fe0f}"),
+    ("asterisk", "*\u{fe0f}\u{20e3}"),
+    ("astonished", "\u{1f632}"),
+    ("athletic_shoe", "\u{1f45f}"),
+    ("atm", "\u{1f3e7}"),
+    ("atom_symbol", "\u{269b}\u{fe0f}"),
+    ("australia", "\u{1f1e6}\u{1f1fa}"),
+    ("avocado", "\u{1f951}"),
+    ("axe", "\u{1fa93}"),
+    ("b", "\u{1f171}\u{fe0f}"),
+    ("baby", "\u{1f476}"),
+    ("baby_bottle", "\u{1f37c}"),
+    ("baby_chick", "\u{1f424}"),
+    ("back", "\u{1f519}"),
+    ("bacon", "\u{1f953}"),
+    ("badminton", "\u{1f3f8}"),
+    ("bagel", "\u{1f96f}"),
+    ("baguette_bread", "\u{1f956}"),
+    ("balance_scale", "\u{2696}\u{fe0f}"),
+    ("balloon", "\u{1f388}"),
+    ("ballot_box", "\u{1f5f3}\u{fe0f}"),
+    ("ballot_box_with_check", "\u{2611}\u{fe0f}"),
+    ("banana", "\u{1f34c}"),
+    ("bangbang", "\u{203c}\u{fe0f}"),
+    ("bank", "\u{1f3e6}"),
+    ("bar_chart", "\u{1f4ca}"),
+    ("baseball", "\u{26be}"),
+    ("basketball", "\u{1f3c0}"),
+    ("bat", "\u{1f987}"),
+    ("bathtub", "\u{1f6c1}"),
+    ("battery", "\u{1f50b}"),
+    ("beach_umbrella", "\u{1f3d6}\u{fe0f}"),
+    ("bear", "\u{1f43b}"),
+    ("bed", "\u{1f6cf}\u{fe0f}"),
+    ("bee", "\u{1f41d}"),
+    ("beer", "\u{1f37a}"),
+    ("beers", "\u{1f37b}"),
+    ("beetle", "\u{1f41e}"),
+    ("beginner", "\u{1f530}"),
+    ("bell", "\u{1f514}"),
+    ("bento", "\u{1f371}"),
+    ("bike", "\u{1f6b2}"),
+    ("bikini", "\u{1f459}"),
+    ("billed_cap", "\u{1f9e2}"),
+    ("biohazard", "\u{2623}\u{fe0f}"),
+    ("bird", "\u{1f426}"),
+    ("birthday", "\u{1f382}"),
+    ("black_circle", "\u{26ab}"),
+    ("black_flag", "\u{1f3f4}"),
+    ("black_heart", "\u{1f5a4}"),
+    ("black_joker", "\u{1f0cf}"),
+    ("black_large_square", "\u{2b1b}"),
+    ("black_nib", "\u{2712}\u{fe0f}"),
+    ("black_square_button", "\u{1f532}"),
+    ("blossom", "\u{1f33c}"),
+    ("blowfish", "\u{1f421}"),
+    ("blue_book", "\u{1f4d8}"),
+    ("blue_car", "\u{1f699}"),
+    ("blue_heart", "\u{1f499}"),
+    ("blue_square", "\u{1f7e6}"),
+    ("blush", "\u{1f60a}"),
+    ("boar", "\u{1f417}"),
+    ("boat", "\u{26f5}"),
+    ("bomb", "\u{1f4a3}"),
+    ("book", "\u{1f4d6}"),
+    ("bookmark", "\u{1f516}"),
+    ("bookmark_tabs", "\u{1f4d1}"),
+    ("books", "\u{1f4da}"),
+    ("boom", "\u{1f4a5}"),
+    ("boot", "\u{1f462}"),
+    ("bouquet", "\u{1f490}"),
+    ("bow", "\u{1f647}"),
+    ("bow_and_arrow", "\u{1f3f9}"),
+    ("bowling", "\u{1f3b3}"),
+    ("boxing_glove", "\u{1f94a}"),
+    ("boy", "\u{1f466}"),
+    ("brain", "\u{1f9e0}"),
+    ("brazil", "\u{1f1e7}\u{1f1f7}"),
+    ("bread", "\u{1f35e}"),
+    ("bridge_at_night", "\u{1f309}"),
+    ("briefcase", "\u{1f4bc}"),
+    ("broccoli", "\u{1f966}"),
+    ("broken_heart", "\u{1f494}"),
+    ("brown_circle", "\u{1f7e4}"),
+    ("brown_heart", "\u{1f90e}"),
+    ("brown_square", "\u{1f7eb}"),
+    ("bug", "\u{1f41b}"),
+    ("bulb", "\u{1f4a1}"),
+    ("bullettrain_front", "\u{1f685}"),
+    ("bullettrain_side", "\u{1f684}"),
+    ("burrito", "\u{1f32f}"),
+    ("bus", "\u{1f68c}"),
+    ("busstop", "\u{1f68f}"),
+    ("butterfly", "\u{1f98b}"),
+    ("cactus", "\u{1f335}"),
+    ("cake", "\u{1f370}"),
+    ("calendar", "\u{1f4c6}"),
+    ("call_me_hand", "\u{1f919}"),
+    ("calling", "\u{1f4f2}"),
+    ("camel", "\u{1f42b}"),
+    ("camera", "\u{1f4f7}"),
+    ("camera_flash", "\u{1f4f8}"),
+    ("camping", "\u{1f3d5}\u{fe0f}"),
+    ("canada", "\u{1f1e8}\u{1f1e6}"),
+    ("cancer", "\u{264b}"),
+    ("candle", "\u{1f56f}\u{fe0f}"),
+    ("candy", "\u{1f36c}"),
+    ("canoe", "\u{1f6f6}"),
+    ("capital_abcd", "\u{1f520}"),
+    ("capricorn", "\u{2651}"),
+    ("car", "\u{1f697}"),
+    ("card_file_box", "\u{1f5c3}\u{fe0f}"),
+    ("card_index", "\u{1f4c7}"),
+    ("card_index_dividers", "\u{1f5c2}\u{fe0f}"),
+    ("carousel_horse", "\u{1f3a0}"),
+    ("carrot", "\u{1f955}"),
+    ("cat", "\u{1f431}"),
+    ("cat2", "\u{1f408}"),
+    ("cd", "\u{1f4bf}"),
+    ("chains", "\u{26d3}\u{fe0f}"),
+    ("champagne", "\u{1f37e}"),
+    ("chart", "\u{1f4b9}"),
+    ("chart_with_downwards_trend", "\u{1f4c9}"),
+    ("chart_with_upwards_trend", "\u{1f4c8}"),
+    ("checkered_flag", "\u{1f3c1}"),
+    ("cheese", "\u{1f9c0}"),
+    ("cherries", "\u{1f352}"),
+    ("cherry_blossom", "\u{1f338}"),
+    ("chess_pawn", "\u{265f}\u{fe0f}"),
+    ("chestnut", "\u{1f330}"),
+    ("chicken", "\u{1f414}"),
+    ("child", "\u{1f9d2}"),
+    ("children_crossing", "\u{1f6b8}"),
+    ("chipmunk", "\u{1f43f}\u{fe0f}"),
+    ("chocolate_bar", "\u{1f36b}"),
+    ("christmas_tree", "\u{1f384}"),
+    ("church", "\u{26ea}"),
+    ("cinema", "\u{1f3a6}"),
+    ("circus_tent", "\u{1f3aa}"),
+    ("city_sunset", "\u{1f306}"),
+    ("cl", "\u{1f191}"),
+    ("clamp", "\u{1f5dc}\u{fe0f}"),
+    ("clap", "\u{1f44f}"),
+    ("clapper", "\u{1f3ac}"),
+    ("clinking_glasses", "\u{1f942}"),
+    ("clipboard", "\u{1f4cb}"),
+    ("closed_book", "\u{1f4d5}"),
+    ("closed_lock_with_key", "\u{1f510}"),
+    ("closed_umbrella", "\u{1f302}"),
+    ("cloud", "\u{2601}\u{fe0f}"),
+    ("cloud_with_lightning_and_rain", "\u{26c8}\u{fe0f}"),
+    ("cloud_with_rain", "\u{1f327}\u{fe0f}"),
+    ("cloud_with_snow", "\u{1f328}\u{fe0f}"),
+    ("clown_face", "\u{1f921}"),
+    ("clubs", "\u{2663}\u{fe0f}"),
+    ("cn", "\u{1f1e8}\u{1f1f3}"),
+    ("cocktail", "\u{1f378}"),
+    ("coconut", "\u{1f965}"),
+    ("coffee", "\u{2615}"),
+    ("coffin", "\u{26b0}\u{fe0f}"),
+    ("cold_face", "\u{1f976}"),
+    ("cold_sweat", "\u{1f630}"),
+    ("collision", "\u{1f4a5}"),
+    ("comet", "\u{2604}\u{fe0f}"),
+    ("compass", "\u{1f9ed}"),
+    ("computer", "\u{1f4bb}"),
+    ("computer_mouse", "\u{1f5b1}\u{fe0f}"),
+    ("confetti_ball", "\u{1f38a}"),
+    ("confounded", "\u{1f616}"),
+    ("confused", "\u{1f615}"),
+    ("construction", "\u{1f6a7}"),
+    ("cookie", "\u{1f36a}"),
+    ("cool", "\u{1f192}"),
+    ("copyright", "\u{a9}\u{fe0f}"),
+    ("corn", "\u{1f33d}"),
+    ("couch_and_lamp", "\u{1f6cb}\u{fe0f}"),
+    ("cow", "\u{1f42e}"),
+    ("cow2", "\u{1f404}"),
+    ("cowboy_hat_face", "\u{1f920}"),
+    ("crayon", "\u{1f58d}\u{fe0f}"),
+    ("credit_card", "\u{1f4b3}"),
+    ("crescent_moon", "\u{1f319}"),
+    ("cricket", "\u{1f997}"),
+    ("crocodile", "\u{1f40a}"),
+    ("croissant", "\u{1f950}"),
+    ("crossed_fingers", "\u{1f91e}"),
+    ("crossed_flags", "\u{1f38c}"),
+    ("crossed_swords", "\u{2694}\u{fe0f}"),
+    ("crown", "\u{1f451}"),
+    ("cry", "\u{1f622}"),
+    ("crying_cat_face", "\u{1f63f}"),
+    ("crystal_ball", "\u{1f52e}"),
+    ("cucumber", "\u{1f952}"),
+    ("cup_with_straw", "\u{1f964}"),
+    ("cupcake", "\u{1f9c1}"),
+    ("cupid", "\u{1f498}"),
+    ("curly_loop", "\u{27b0}"),
+    ("curry", "\u{1f35b}"),
+    ("cursing_face", "\u{1f92c}"),
+    ("cyclone", "\u{1f300}"),
+    ("dagger", "\u{1f5e1}\u{fe0f}"),
+    ("dark_sunglasses", "\u{1f576}\u{fe0f}"),
+    ("dart", "\u{1f3af}"),
+    ("dash", "\u{1f4a8}"),
+    ("date", "\u{1f4c5}"),
+    ("de", "\u{1f1e9}\u{1f1ea}"),
+    ("deciduous_tree", "\u{1f333}"),
+    ("desert", "\u{1f3dc}\u{fe0f}"),
+    ("desert_island", "\u{1f3dd}\u{fe0f}"),
+    ("desktop_computer", "\u{1f5a5}\u{fe0f}"),
+    ("diamond_shape_with_a_dot_inside", "\u{1f4a0}"),
+    ("diamonds", "\u{2666}\u{fe0f}"),
+    ("disappointed", "\u{1f61e}"),
+    ("disappointed_relieved", "\u{1f625}"),
+    ("dizzy", "\u{1f4ab}"),
+    ("dizzy_face", "\u{1f635}"),
+    ("dna", "\u{1f9ec}"),
+    ("dog", "\u{1f436}"),
+    ("dog2", "\u{1f415}"),
+    ("dollar", "\u{1f4b5}"),
+    ("dolphin", "\u{1f42c}"),
+    ("door", "\u{1f6aa}"),
+    ("doughnut", "\u{1f369}"),
+    ("dove", "\u{1f54a}\u{fe0f}"),
+    ("dragon", "\u{1f409}"),
+    ("dragon_face", "\u{1f432}"),
+    ("dress", "\u{1f457}"),
+    ("dromedary_camel", "\u{1f42a}"),
+    ("drooling_face", "\u{1f924}"),
+    ("droplet", "\u{1f4a7}"),
+    ("drum", "\u{1f941}"),
+    ("duck", "\u{1f986}"),
+    ("dumpling", "\u{1f95f}"),
+    ("dvd", "\u{1f4c0}"),
+    ("e-mail", "\u{1f4e7}"),
+    ("eagle", "\u{1f985}"),
+    ("ear_of_rice", "\u{1f33e}"),
+    ("earth_africa", "\u{1f30d}"),
+    ("earth_americas", "\u{1f30e}"),
+    ("earth_asia", "\u{1f30f}"),
+    ("egg", "\u{1f95a}"),
+    ("eggplant", "\u{1f346}"),
+    ("eight", "8\u{fe0f}\u{20e3}"),
+    ("eight_pointed_black_star", "\u{2734}\u{fe0f}"),
+    ("eight_spoked_asterisk", "\u{2733}\u{fe0f}"),
+    ("electric_plug", "\u{1f50c}"),
+    ("elephant", "\u{1f418}"),
+    ("email", "\u{2709}\u{fe0f}"),
+    ("end", "\u{1f51a}"),
+    ("envelope", "\u{2709}\u{fe0f}"),
+    ("envelope_with_arrow", "\u{1f4e9}"),
+    ("es", "\u{1f1ea}\u{1f1f8}"),
+    ("eu", "\u{1f1ea}\u{1f1fa}"),
+    ("euro", "\u{1f4b6}"),
+    ("european_castle", "\u{1f3f0}"),
+    ("evergreen_tree", "\u{1f332}"),
+    ("exclamation", "\u{2757}"),
+    ("exploding_head", "\u{1f92f}"),
+    ("expressionless", "\u{1f611}"),
+    ("eye", "\u{1f441}\u{fe0f}"),
+    ("eyeglasses", "\u{1f453}"),
+    ("eyes", "\u{1f440}"),
+    ("face_with_head_bandage", "\u{1f915}"),
+    ("face_with_thermometer", "\u{1f912}"),
+    ("facepalm", "\u{1f926}"),
+    ("facepunch", "\u{1f44a}"),
+    ("factory", "\u{1f3ed}"),
+    ("fallen_leaf", "\u{1f342}"),
+    ("fast_forward", "\u{23e9}"),
+    ("fax", "\u{1f4e0}"),
+    ("fearful", "\u{1f628}"),
+    ("feet", "\u{1f43e}"),
+    ("ferris_wheel", "\u{1f3a1}"),
+    ("file_cabinet", "\u{1f5c4}\u{fe0f}"),
+    ("file_folder", "\u{1f4c1}"),
+    ("film_projector", "\u{1f4fd}\u{fe0f}"),
+    ("film_strip", "\u{1f39e}\u{fe0f}"),
+    ("fire", "\u{1f525}"),
+    ("fire_engine", "\u{1f692}"),
+    ("firecracker", "\u{1f9e8}"),
+    ("fireworks", "\u{1f386}"),
+    ("fish", "\u{1f41f}"),
+    ("fishing_pole_and_fish", "\u{1f3a3}"),
+    ("fist", "\u{270a}"),
+    ("fist_left", "\u{1f91b}"),
+    ("fist_oncoming", "\u{1f44a}"),
+    ("fist_raised", "\u{270a}"),
+    ("fist_right", "\u{1f91c}"),
+    ("five", "5\u{fe0f}\u{20e3}"),
+    ("flashlight", "\u{1f526}"),
+    ("fleur_de_lis", "\u{269c}\u{fe0f}"),
+    ("flight_arrival", "\u{1f6ec}"),
+    ("flight_departure", "\u{1f6eb}"),
+    ("flipper", "\u{1f42c}"),
+    ("floppy_disk", "\u{1f4be}"),
+    ("flushed", "\u{1f633}"),
+    ("fog", "\u{1f32b}\u{fe0f}"),
+    ("foggy", "\u{1f301}"),
+    ("football", "\u{1f3c8}"),
+    ("fork_and_knife", "\u{1f374}"),
+    ("fountain_pen", "\u{1f58b}\u{fe0f}"),
+    ("four", "4\u{fe0f}\u{20e3}"),
+    ("four_leaf_clover", "\u{1f340}"),
+    ("fox_face", "\u{1f98a}"),
+    ("fr", "\u{1f1eb}\u{1f1f7}"),
+    ("free", "\u{1f193}"),
+    ("fried_egg", "\u{1f373}"),
+    ("fried_shrimp", "\u{1f364}"),
+    ("fries", "\u{1f35f}"),
+    ("frog", "\u{1f438}"),
+    ("frowning", "\u{1f626}"),
+    ("frowning_face", "\u{2639}\u{fe0f}"),
+    ("fu", "\u{1f595}"),
+    ("fuelpump", "\u{26fd}"),
+    ("full_moon", "\u{1f315}"),
+    ("game_die", "\u{1f3b2}"),
+    ("garlic", "\u{1f9c4}"),
+    ("gb", "\u{1f1ec}\u{1f1e7}"),
+    ("gear", "\u{2699}\u{fe0f}"),
+    ("gem", "\u{1f48e}"),
+    ("gemini", "\u{264a}"),
+    ("ghost", "\u{1f47b}"),
+    ("gift", "\u{1f381}"),
+    ("gift_heart", "\u{1f49d}"),
+    ("giraffe", "\u{1f992}"),
+    ("girl", "\u{1f467}"),
+    ("globe_with_meridians", "\u{1f310}"),
+    ("gloves", "\u{1f9e4}"),
+    ("goat", "\u{1f410}"),
+    ("goggles", "\u{1f97d}"),
+    ("golf", "\u{26f3}"),
+    ("grapes", "\u{1f347}"),
+    ("green_apple", "\u{1f34f}"),
+    ("green_book", "\u{1f4d7}"),
+    ("green_circle", "\u{1f7e2}"),
+    ("green_heart", "\u{1f49a}"),
+    ("green_square", "\u{1f7e9}"),
+    ("grey_exclamation", "\u{2755}"),
+    ("grey_question", "\u{2754}"),
+    ("grimacing", "\u{1f62c}"),
+    ("grin", "\u{1f601}"),
+    ("grinning", "\u{1f600}"),
+    ("guitar", "\u{1f3b8}"),
+    ("gun", "\u{1f52b}"),
+    ("hamburger", "\u{1f354}"),
+    ("hammer", "\u{1f528}"),
+    ("hammer_and_pick", "\u{2692}\u{fe0f}"),
+    ("hammer_and_wrench", "\u{1f6e0}\u{fe0f}"),
+    ("hamster", "\u{1f439}"),
+    ("hand", "\u{270b}"),
+    ("hand_over_mouth", "\u{1f92d}"),
+    ("handbag", "\u{1f45c}"),
+    ("handshake", "\u{1f91d}"),
+    ("hankey", "\u{1f4a9}"),
+    ("hash", "#\u{fe0f}\u{20e3}"),
+    ("hatched_chick", "\u{1f425}"),
+    ("hatching_chick", "\u{1f423}"),
+    ("headphones", "\u{1f3a7}"),
+    ("hear_no_evil", "\u{1f649}"),
+    ("heart", "\u{2764}\u{fe0f}"),
+    ("heart_decoration", "\u{1f49f}"),
+    ("heart_eyes", "\u{1f60d}"),
+    ("heart_eyes_cat", "\u{1f63b}"),
+    ("heartbeat", "\u{1f493}"),
+    ("heartpulse", "\u{1f497}"),
+    ("hearts", "\u{2665}\u{fe0f}"),
+    ("heavy_check_mark", "\u{2714}\u{fe0f}"),
+    ("heavy_division_sign", "\u{2797}"),
+    ("heavy_exclamation_mark", "\u{2757}"),
+    ("heavy_heart_exclamation", "\u{2763}\u{fe0f}"),
+    ("heavy_minus_sign", "\u{2796}"),
+    ("heavy_plus_sign", "\u{2795}"),
+    ("hedgehog", "\u{1f994}"),
+    ("helicopter", "\u{1f681}"),
+    ("herb", "\u{1f33f}"),
+    ("hibiscus", "\u{1f33a}"),
+    ("high_brightness", "\u{1f506}"),
+    ("high_heel", "\u{1f460}"),
+    ("hocho", "\u{1f52a}"),
+    ("hole", "\u{1f573}\u{fe0f}"),
+    ("honey_pot", "\u{1f36f}"),
+    ("honeybee", "\u{1f41d}"),
+    ("horse", "\u{1f434}"),
+    ("hospital", "\u{1f3e5}"),
+    ("hot_face", "\u{1f975}"),
+    ("hot_pepper", "\u{1f336}\u{fe0f}"),
+    ("hotdog", "\u{1f32d}"),
+    ("hotel", "\u{1f3e8}"),
+    ("hourglass", "\u{231b}"),
+    ("hourglass_flowing_sand", "\u{23f3}"),
+    ("house", "\u{1f3e0}"),
+    ("house_with_garden", "\u{1f3e1}"),
+    ("hugs", "\u{1f917}"),
+    ("hushed", "\u{1f62f}"),
+    ("ice_cream", "\u{1f368}"),
+    ("ice_skate", "\u{26f8}\u{fe0f}"),
+    ("icecream", "\u{1f366}"),
+    ("id", "\u{1f194}"),
+    ("imp", "\u{1f47f}"),
+    ("inbox_tray", "\u{1f4e5}"),
+    ("incoming_envelope", "\u{1f4e8}"),
+    ("india", "\u{1f1ee}\u{1f1f3}"),
+    ("infinity", "\u{267e}\u{fe0f}"),
+    ("information_source", "\u{2139}\u{fe0f}"),
+    ("innocent", "\u{1f607}"),
+    ("interrobang", "\u{2049}\u{fe0f}"),
+    ("iphone", "\u{1f4f1}"),
+    ("it", "\u{1f1ee}\u{1f1f9}"),
+    ("izakaya_lantern", "\u{1f3ee}"),
+    ("jack_o_lantern", "\u{1f383}"),
+    ("japan", "\u{1f5fe}"),
+    ("japanese_goblin", "\u{1f47a}"),
+    ("japanese_ogre", "\u{1f479}"),
+    ("jeans", "\u{1f456}"),
+    ("jigsaw", "\u{1f9e9}"),
+    ("joy", "\u{1f602}"),
+    ("joy_cat", "\u{1f639}"),
+    ("joystick", "\u{1f579}\u{fe0f}"),
+    ("jp", "\u{1f1ef}\u{1f1f5}"),
+    ("key", "\u{1f511}"),
+    ("keyboard", "\u{2328}\u{fe0f}"),
+    ("keycap_ten", "\u{1f51f}"),
+    ("kick_scooter", "\u{1f6f4}"),
+    ("kimono", "\u{1f458}"),
+    ("kiss", "\u{1f48b}"),
+    ("kissing", "\u{1f617}"),
+    ("kissing_cat", "\u{1f63d}"),
+    ("kissing_closed_eyes", "\u{1f61a}"),
+    ("kissing_heart", "\u{1f618}"),
+    ("kissing_smiling_eyes", "\u{1f619}"),
+    ("kite", "\u{1fa81}"),
+    ("kiwi_fruit", "\u{1f95d}"),
+    ("knife", "\u{1f52a}"),
+    ("koala", "\u{1f428}"),
+    ("kr", "\u{1f1f0}\u{1f1f7}"),
+    ("lab_coat", "\u{1f97c}"),
+    ("label", "\u{1f3f7}\u{fe0f}"),
+    ("lantern", "\u{1f3ee}"),
+    ("large_blue_circle", "\u{1f535}"),
+    ("large_blue_diamond", "\u{1f537}"),
+    ("large_orange_diamond", "\u{1f536}"),
+    ("latin_cross", "\u{271d}\u{fe0f}"),
+    ("laughing", "\u{1f606}"),
+    ("leaves", "\u{1f343}"),
+    ("ledger", "\u{1f4d2}"),
+    ("left_right_arrow", "\u{2194}\u{fe0f}"),
+    ("leftwards_arrow_with_hook", "\u{21a9}\u{fe0f}"),
+    ("lemon", "\u{1f34b}"),
+    ("leo", "\u{264c}"),
+    ("libra", "\u{264e}"),
+    ("link", "\u{1f517}"),
+    ("lion", "\u{1f981}"),
+    ("lips", "\u{1f444}"),
+    ("lipstick", "\u{1f484}"),
+    ("lizard", "\u{1f98e}"),
+    ("lock", "\u{1f512}"),
+    ("lock_with_ink_pen", "\u{1f50f}"),
+    ("lollipop", "\u{1f36d}"),
+    ("loop", "\u{27bf}"),
+    ("loud_sound", "\u{1f50a}"),
+    ("loudspeaker", "\u{1f4e2}"),
+    ("love_letter", "\u{1f48c}"),
+    ("love_you_gesture", "\u{1f91f}"),
+    ("low_brightness", "\u{1f505}"),
+    ("lying_face", "\u{1f925}"),
+    ("m", "\u{24c2}\u{fe0f}"),
+    ("mag", "\u{1f50d}"),
+    ("mag_right", "\u{1f50e}"),
+    ("magnet", "\u{1f9f2}"),
+    ("mahjong", "\u{1f004}"),
+    ("mailbox", "\u{1f4eb}"),
+    ("mailbox_closed", "\u{1f4ea}"),
+    ("mailbox_with_mail", "\u{1f4ec}"),
+    ("mailbox_with_no_mail", "\u{1f4ed}"),
+    ("man", "\u{1f468}"),
+    ("mandarin", "\u{1f34a}"),
+    ("mango", "\u{1f96d}"),
+    ("mans_shoe", "\u{1f45e}"),
+    ("maple_leaf", "\u{1f341}"),
+    ("mask", "\u{1f637}"),
+    ("meat_on_bone", "\u{1f356}"),
+    ("medal_sports", "\u{1f3c5}"),
+    ("mega", "\u{1f4e3}"),
+    ("melon", "\u{1f348}"),
+    ("memo", "\u{1f4dd}"),
+    ("menorah", "\u{1f54e}"),
+    ("mens", "\u{1f6b9}"),
+    ("metal", "\u{1f918}"),
+    ("metro", "\u{1f687}"),
+    ("microbe", "\u{1f9a0}"),
+    ("microphone", "\u{1f3a4}"),
+    ("microscope", "\u{1f52c}"),
+    ("middle_finger", "\u{1f595}"),
+    ("milk_glass", "\u{1f95b}"),
+    ("milky_way", "\u{1f30c}"),
+    ("minidisc", "\u{1f4bd}"),
+    ("mobile_phone_off", "\u{1f4f4}"),
+    ("money_mouth_face", "\u{1f911}"),
+    ("money_with_wings", "\u{1f4b8}"),
+    ("moneybag", "\u{1f4b0}"),
+    ("monkey", "\u{1f412}"),
+    ("monkey_face", "\u{1f435}"),
+    ("monocle_face", "\u{1f9d0}"),
+    ("mortar_board", "\u{1f393}"),
+    ("mosquito", "\u{1f99f}"),
+    ("motorcycle", "\u{1f3cd}\u{fe0f}"),
+    ("mount_fuji", "\u{1f5fb}"),
+    ("mountain", "\u{26f0}\u{fe0f}"),
+    ("mountain_snow", "\u{1f3d4}\u{fe0f}"),
+    ("mouse", "\u{1f42d}"),
+    ("mouse2", "\u{1f401}"),
+    ("movie_camera", "\u{1f3a5}"),
+    ("moyai", "\u{1f5ff}"),
+    ("muscle", "\u{1f4aa}"),
+    ("mushroom", "\u{1f344}"),
+    ("musical_keyboard", "\u{1f3b9}"),
+    ("musical_note", "\u{1f3b5}"),
+    ("musical_score", "\u{1f3bc}"),
+    ("mute", "\u{1f507}"),
+    ("nail_care", "\u{1f485}"),
+    ("name_badge", "\u{1f4db}"),
+    ("nauseated_face", "\u{1f922}"),
+    ("necktie", "\u{1f454}"),
+    ("negative_squared_cross_mark", "\u{274e}"),
+    ("nerd_face", "\u{1f913}"),
+    ("neutral_face", "\u{1f610}"),
+    ("new", "\u{1f195}"),
+    ("new_moon", "\u{1f311}"),
+    ("newspaper", "\u{1f4f0}"),
+    ("newspaper_roll", "\u{1f5de}\u{fe0f}"),
+    ("ng", "\u{1f196}"),
+    ("night_with_stars", "\u{1f303}"),
+    ("nine", "9\u{fe0f}\u{20e3}"),
+    ("no_bell", "\u{1f515}"),
+    ("no_bicycles", "\u{1f6b3}"),
+    ("no_entry", "\u{26d4}"),
+    ("no_entry_sign", "\u{1f6ab}"),
+    ("no_mouth", "\u{1f636}"),
+    ("no_smoking", "\u{1f6ad}"),
+    ("notebook", "\u{1f4d3}"),
+    ("notebook_with_decorative_cover", "\u{1f4d4}"),
+    ("notes", "\u{1f3b6}"),
+    ("nut_and_bolt", "\u{1f529}"),
+    ("o", "\u{2b55}"),
+    ("o2", "\u{1f17e}\u{fe0f}"),
+    ("ocean", "\u{1f30a}"),
+    ("octopus", "\u{1f419}"),
+    ("office", "\u{1f3e2}"),
+    ("ok", "\u{1f197}"),
+    ("ok_hand", "\u{1f44c}"),
+    ("old_key", "\u{1f5dd}\u{fe0f}"),
+    ("older_man", "\u{1f474}"),
+    ("older_woman", "\u{1f475}"),
+    ("om", "\u{1f549}\u{fe0f}"),
+    ("on", "\u{1f51b}"),
+    ("one", "1\u{fe0f}\u{20e3}"),
+    ("onion", "\u{1f9c5}"),
+    ("open_book", "\u{1f4d6}"),
+    ("open_file_folder", "\u{1f4c2}"),
+    ("open_hands", "\u{1f450}"),
+    ("open_mouth", "\u{1f62e}"),
+    ("orange", "\u{1f34a}"),
+    ("orange_book", "\u{1f4d9}"),
+    ("orange_circle", "\u{1f7e0}"),
+    ("orange_heart", "\u{1f9e1}"),
+    ("orange_square", "\u{1f7e7}"),
+    ("outbox_tray", "\u{1f4e4}"),
+    ("owl", "\u{1f989}"),
+    ("ox", "\u{1f402}"),
+    ("package", "\u{1f4e6}"),
+    ("page_facing_up", "\u{1f4c4}"),
+    ("page_with_curl", "\u{1f4c3}"),
+    ("pager", "\u{1f4df}"),
+    ("paintbrush", "\u{1f58c}\u{fe0f}"),
+    ("palm_tree", "\u{1f334}"),
+    ("palms_up_together", "\u{1f932}"),
+    ("pancakes", "\u{1f95e}"),
+    ("panda_face", "\u{1f43c}"),
+    ("paperclip", "\u{1f4ce}"),
+    ("paperclips", "\u{1f587}\u{fe0f}"),
+    ("parachute", "\u{1fa82}"),
+    ("parking", "\u{1f17f}\u{fe0f}"),
+    ("part_alternation_mark", "\u{303d}\u{fe0f}"),
+    ("partly_sunny", "\u{26c5}"),
+    ("partying_face", "\u{1f973}"),
+    ("pause_button", "\u{23f8}\u{fe0f}"),
+    ("paw_prints", "\u{1f43e}"),
+    ("peace_symbol", "\u{262e}\u{fe0f}"),
+    ("peach", "\u{1f351}"),
+    ("peanuts", "\u{1f95c}"),
+    ("pear", "\u{1f350}"),
+    ("pen", "\u{1f58a}\u{fe0f}"),
+    ("pencil", "\u{1f4dd}"),
+    ("pencil2", "\u{270f}\u{fe0f}"),
+    ("penguin", "\u{1f427}"),
+    ("pensive", "\u{1f614}"),
+    ("performing_arts", "\u{1f3ad}"),
+    ("persevere", "\u{1f623}"),
+    ("petri_dish", "\u{1f9eb}"),
+    ("phone", "\u{260e}\u{fe0f}"),
+    ("pick", "\u{26cf}\u{fe0f}"),
+    ("pie", "\u{1f967}"),
+    ("pig", "\u{1f437}"),
+    ("pig2", "\u{1f416}"),
+    ("pig_nose", "\u{1f43d}"),
+    ("pill", "\u{1f48a}"),
+    ("pinching_hand", "\u{1f90f}"),
+    ("pineapple", "\u{1f34d}"),
+    ("ping_pong", "\u{1f3d3}"),
+    ("pirate_flag", "\u{1f3f4}\u{200d}\u{2620}\u{fe0f}"),
+    ("pisces", "\u{2653}"),
+    ("pizza", "\u{1f355}"),
+    ("place_of_worship", "\u{1f6d0}"),
+    ("pleading_face", "\u{1f97a}"),
+    ("point_down", "\u{1f447}"),
+    ("point_left", "\u{1f448}"),
+    ("point_right", "\u{1f449}"),
+    ("point_up", "\u{261d}\u{fe0f}"),
+    ("point_up_2", "\u{1f446}"),
+    ("police_car", "\u{1f693}"),
+    ("poop", "\u{1f4a9}"),
+    ("popcorn", "\u{1f37f}"),
+    ("postbox", "\u{1f4ee}"),
+    ("potato", "\u{1f954}"),
+    ("poultry_leg", "\u{1f357}"),
+    ("pound", "\u{1f4b7}"),
+    ("pout", "\u{1f621}"),
+    ("pouting_cat", "\u{1f63e}"),
+    ("pray", "\u{1f64f}"),
+    ("pretzel", "\u{1f968}"),
+    ("printer", "\u{1f5a8}\u{fe0f}"),
+    ("punch", "\u{1f44a}"),
+    ("purple_circle", "\u{1f7e3}"),
+    ("purple_heart", "\u{1f49c}"),
+    ("purple_square", "\u{1f7ea}"),
+    ("purse", "\u{1f45b}"),
+    ("pushpin", "\u{1f4cc}"),
+    ("put_litter_in_its_place", "\u{1f6ae}"),
+    ("question", "\u{2753}"),
+    ("rabbit", "\u{1f430}"),
+    ("rabbit2", "\u{1f407}"),
+    ("racehorse", "\u{1f40e}"),
+    ("racing_car", "\u{1f3ce}\u{fe0f}"),
+    ("radio", "\u{1f4fb}"),
+    ("radio_button", "\u{1f518}"),
+    ("radioactive", "\u{2622}\u{fe0f}"),
+    ("rage", "\u{1f621}"),
+    ("rainbow", "\u{1f308}"),
+    ("rainbow_flag", "\u{1f3f3}\u{fe0f}\u{200d}\u{1f308}"),
+    ("raised_back_of_hand", "\u{1f91a}"),
+    ("raised_eyebrow", "\u{1f928}"),
+    ("raised_hand", "\u{270b}"),
+    ("raised_hand_with_fingers_splayed", "\u{1f590}\u{fe0f}"),
+    ("raised_hands", "\u{1f64c}"),
+    ("ram", "\u{1f40f}"),
+    ("ramen", "\u{1f35c}"),
+    ("rat", "\u{1f400}"),
+    ("receipt", "\u{1f9fe}"),
+    ("record_button", "\u{23fa}\u{fe0f}"),
+    ("recycle", "\u{267b}\u{fe0f}"),
+    ("red_car", "\u{1f697}"),
+    ("red_circle", "\u{1f534}"),
+    ("red_square", "\u{1f7e5}"),
+    ("registered", "\u{ae}\u{fe0f}"),
+    ("relaxed", "\u{263a}\u{fe0f}"),
+    ("relieved", "\u{1f60c}"),
+    ("repeat", "\u{1f501}"),
+    ("repeat_one", "\u{1f502}"),
+    ("rescue_worker_helmet", "\u{26d1}\u{fe0f}"),
+    ("restroom", "\u{1f6bb}"),
+    ("revolving_hearts", "\u{1f49e}"),
+    ("rewind", "\u{23ea}"),
+    ("rhinoceros", "\u{1f98f}"),
+    ("ribbon", "\u{1f380}"),
+    ("rice", "\u{1f35a}"),
+    ("ring", "\u{1f48d}"),
+    ("robot", "\u{1f916}"),
+    ("rocket", "\u{1f680}"),
+    ("rofl", "\u{1f923}"),
+    ("roll_eyes", "\u{1f644}"),
+    ("roller_coaster", "\u{1f3a2}"),
+    ("rooster", "\u{1f413}"),
+    ("rose", "\u{1f339}"),
+    ("rotating_light", "\u{1f6a8}"),
+    ("round_pushpin", "\u{1f4cd}"),
+    ("ru", "\u{1f1f7}\u{1f1fa}"),
+    ("rugby_football", "\u{1f3c9}"),
+    ("sagittarius", "\u{2650}"),
+    ("sailboat", "\u{26f5}"),
+    ("sake", "\u{1f376}"),
+    ("salt", "\u{1f9c2}"),
+    ("sandwich", "\u{1f96a}"),
+    ("satellite", "\u{1f4e1}"),
+    ("satisfied", "\u{1f606}"),
+    ("sauropod", "\u{1f995}"),
+    ("saxophone", "\u{1f3b7}"),
+    ("scarf", "\u{1f9e3}"),
+    ("school", "\u{1f3eb}"),
+    ("school_satchel", "\u{1f392}"),
+    ("scissors", "\u{2702}\u{fe0f}"),
+    ("scorpion", "\u{1f982}"),
+    ("scorpius", "\u{264f}"),
+    ("scream", "\u{1f631}"),
+    ("scream_cat", "\u{1f640}"),
+    ("scroll", "\u{1f4dc}"),
+    ("seat", "\u{1f4ba}"),
+    ("see_no_evil", "\u{1f648}"),
+    ("seedling", "\u{1f331}"),
+    ("selfie", "\u{1f933}"),
+    ("seven", "7\u{fe0f}\u{20e3}"),
+    ("shamrock", "\u{2618}\u{fe0f}"),
+    ("shark", "\u{1f988}"),
+    ("sheep", "\u{1f411}"),
+    ("shell", "\u{1f41a}"),
+    ("shield", "\u{1f6e1}\u{fe0f}"),
+    ("ship", "\u{1f6a2}"),
+    ("shirt", "\u{1f455}"),
+    ("shit", "\u{1f4a9}"),
+    ("shoe", "\u{1f45e}"),
+    ("shopping", "\u{1f6cd}\u{fe0f}"),
+    ("shopping_cart", "\u{1f6d2}"),
+    ("shower", "\u{1f6bf}"),
+    ("shrug", "\u{1f937}"),
+    ("shushing_face", "\u{1f92b}"),
+    ("signal_strength", "\u{1f4f6}"),
+    ("six", "6\u{fe0f}\u{20e3}"),
+    ("ski", "\u{1f3bf}"),
+    ("skull", "\u{1f480}"),
+    ("skull_and_crossbones", "\u{2620}\u{fe0f}"),
+    ("sleeping", "\u{1f634}"),
+    ("sleepy", "\u{1f62a}"),
+    ("slightly_frowning_face", "\u{1f641}"),
+    ("slightly_smiling_face", "\u{1f642}"),
+    ("slot_machine", "\u{1f3b0}"),
+    ("small_blue_diamond", "\u{1f539}"),
+    ("small_orange_diamond", "\u{1f538}"),
+    ("small_red_triangle", "\u{1f53a}"),
+    ("small_red_triangle_down", "\u{1f53b}"),
+    ("smile", "\u{1f604}"),
+    ("smile_cat", "\u{1f638}"),
+    ("smiley", "\u{1f603}"),
+    ("smiley_cat", "\u{1f63a}"),
+    ("smiling_face_with_three_hearts", "\u{1f970}"),
+    ("smiling_imp", "\u{1f608}"),
+    ("smirk", "\u{1f60f}"),
+    ("smirk_cat", "\u{1f63c}"),
+    ("smoking", "\u{1f6ac}"),
+    ("snail", "\u{1f40c}"),
+    ("snake", "\u{1f40d}"),
+    ("sneezing_face", "\u{1f927}"),
+    ("snowflake", "\u{2744}\u{fe0f}"),
+    ("snowman", "\u{26c4}"),
+    ("snowman_with_snow", "\u{2603}\u{fe0f}"),
+    ("sob", "\u{1f62d}"),
+    ("soccer", "\u{26bd}"),
+    ("softball", "\u{1f94e}"),
+    ("soon", "\u{1f51c}"),
+    ("sos", "\u{1f198}"),
+    ("sound", "\u{1f509}"),
+    ("space_invader", "\u{1f47e}"),
+    ("spades", "\u{2660}\u{fe0f}"),
+    ("spaghetti", "\u{1f35d}"),
+    ("sparkle", "\u{2747}\u{fe0f}"),
+    ("sparkler", "\u{1f387}"),
+    ("sparkles", "\u{2728}"),
+    ("sparkling_heart", "\u{1f496}"),
+    ("speak_no_evil", "\u{1f64a}"),
+    ("speaker", "\u{1f508}"),
+    ("speech_balloon", "\u{1f4ac}"),
+    ("speedboat", "\u{1f6a4}"),
+    ("spider", "\u{1f577}\u{fe0f}"),
+    ("spider_web", "\u{1f578}\u{fe0f}"),
+    ("spiral_calendar", "\u{1f5d3}\u{fe0f}"),
+    ("spiral_notepad", "\u{1f5d2}\u{fe0f}"),
+    ("spoon", "\u{1f944}"),
+    ("star", "\u{2b50}"),
+    ("star2", "\u{1f31f}"),
+    ("star_of_david", "\u{2721}\u{fe0f}"),
+    ("star_struck", "\u{1f929}"),
+    ("stars", "\u{1f320}"),
+    ("station", "\u{1f689}"),
+    ("statue_of_liberty", "\u{1f5fd}"),
+    ("steam_locomotive", "\u{1f682}"),
+    ("stop_button", "\u{23f9}\u{fe0f}"),
+    ("stopwatch", "\u{23f1}\u{fe0f}"),
+    ("straight_ruler", "\u{1f4cf}"),
+    ("strawberry", "\u{1f353}"),
+    ("stuck_out_tongue", "\u{1f61b}"),
+    ("stuck_out_tongue_closed_eyes", "\u{1f61d}"),
+    ("stuck_out_tongue_winking_eye", "\u{1f61c}"),
+    ("sunflower", "\u{1f33b}"),
+    ("sunglasses", "\u{1f60e}"),
+    ("sunny", "\u{2600}\u{fe0f}"),
+    ("sunrise", "\u{1f305}"),
+    ("sushi", "\u{1f363}"),
+    ("sweat", "\u{1f613}"),
+    ("sweat_drops", "\u{1f4a6}"),
+    ("sweat_smile", "\u{1f605}"),
+    ("symbols", "\u{1f523}"),
+    ("syringe", "\u{1f489}"),
+    ("t-rex", "\u{1f996}"),
+    ("taco", "\u{1f32e}"),
+    ("tada", "\u{1f389}"),
+    ("tanabata_tree", "\u{1f38b}"),
+    ("tangerine", "\u{1f34a}"),
+    ("taurus", "\u{2649}"),
+    ("taxi", "\u{1f695}"),
+    ("tea", "\u{1f375}"),
+    ("technologist", "\u{1f9d1}\u{200d}\u{1f4bb}"),
+    ("teddy_bear", "\u{1f9f8}"),
+    ("telephone", "\u{260e}\u{fe0f}"),
+    ("telephone_receiver", "\u{1f4de}"),
+    ("telescope", "\u{1f52d}"),
+    ("tennis", "\u{1f3be}"),
+    ("tent", "\u{26fa}"),
+    ("test_tube", "\u{1f9ea}"),
+    ("thinking", "\u{1f914}"),
+    ("thought_balloon", "\u{1f4ad}"),
+    ("thread", "\u{1f9f5}"),
+    ("three", "3\u{fe0f}\u{20e3}"),
+    ("thumbsdown", "\u{1f44e}"),
+    ("thumbsup", "\u{1f44d}"),
+    ("ticket", "\u{1f3ab}"),
+    ("tiger", "\u{1f42f}"),
+    ("tiger2", "\u{1f405}"),
+    ("timer_clock", "\u{23f2}\u{fe0f}"),
+    ("tired_face", "\u{1f62b}"),
+    ("tm", "\u{2122}\u{fe0f}"),
+    ("toilet", "\u{1f6bd}"),
+    ("tomato", "\u{1f345}"),
+    ("tongue", "\u{1f445}"),
+    ("toolbox", "\u{1f9f0}"),
+    ("top", "\u{1f51d}"),
+    ("tophat", "\u{1f3a9}"),
+    ("tornado", "\u{1f32a}\u{fe0f}"),
+    ("trackball", "\u{1f5b2}\u{fe0f}"),
+    ("tractor", "\u{1f69c}"),
+    ("traffic_light", "\u{1f6a5}"),
+    ("train", "\u{1f68b}"),
+    ("train2", "\u{1f686}"),
+    ("triangular_flag_on_post", "\u{1f6a9}"),
+    ("triangular_ruler", "\u{1f4d0}"),
+    ("trident", "\u{1f531}"),
+    ("triumph", "\u{1f624}"),
+    ("trophy", "\u{1f3c6}"),
+    ("tropical_drink", "\u{1f379}"),
+    ("tropical_fish", "\u{1f420}"),
+    ("truck", "\u{1f69a}"),
+    ("trumpet", "\u{1f3ba}"),
+    ("tshirt", "\u{1f455}"),
+    ("tulip", "\u{1f337}"),
+    ("tumbler_glass", "\u{1f943}"),
+    ("turkey", "\u{1f983}"),
+    ("turtle", "\u{1f422}"),
+    ("tv", "\u{1f4fa}"),
+    ("twisted_rightwards_arrows", "\u{1f500}"),
+    ("two", "2\u{fe0f}\u{20e3}"),
+    ("two_hearts", "\u{1f495}"),
+    ("uk", "\u{1f1ec}\u{1f1e7}"),
+    ("umbrella", "\u{2614}"),
+    ("unamused", "\u{1f612}"),
+    ("underage", "\u{1f51e}"),
+    ("unicorn", "\u{1f984}"),
+    ("unlock", "\u{1f513}"),
+    ("up", "\u{1f199}"),
+    ("upside_down_face", "\u{1f643}"),
+    ("us", "\u{1f1fa}\u{1f1f8}"),
+    ("v", "\u{270c}\u{fe0f}"),
+    ("vertical_traffic_light", "\u{1f6a6}"),
+    ("vhs", "\u{1f4fc}"),
+    ("vibration_mode", "\u{1f4f3}"),
+    ("video_camera", "\u{1f4f9}"),
+    ("video_game", "\u{1f3ae}"),
+    ("violin", "\u{1f3bb}"),
+    ("virgo", "\u{264d}"),
+    ("volcano", "\u{1f30b}"),
+    ("volleyball", "\u{1f3d0}"),
+    ("vomiting_face", "\u{1f92e}"),
+    ("vs", "\u{1f19a}"),
+    ("vulcan_salute", "\u{1f596}"),
+    ("warning", "\u{26a0}\u{fe0f}"),
+    ("wastebasket", "\u{1f5d1}\u{fe0f}"),
+    ("watch", "\u{231a}"),
+    ("watermelon", "\u{1f349}"),
+    ("wave", "\u{1f44b}"),
+    ("wavy_dash", "\u{3030}\u{fe0f}"),
+    ("weary", "\u{1f629}"),
+    ("whale", "\u{1f433}"),
+    ("whale2", "\u{1f40b}"),
+    ("wheel_of_dharma", "\u{2638}\u{fe0f}"),
+    ("wheelchair", "\u{267f}"),
+    ("white_check_mark", "\u{2705}"),
+    ("white_circle", "\u{26aa}"),
+    ("white_flag", "\u{1f3f3}\u{fe0f}"),
+    ("white_heart", "\u{1f90d}"),
+    ("white_large_square", "\u{2b1c}"),
+    ("white_square_button", "\u{1f533}"),
+    ("wilted_flower", "\u{1f940}"),
+    ("wine_glass", "\u{1f377}"),
+    ("wink", "\u{1f609}"),
+    ("wolf", "\u{1f43a}"),
+    ("woman", "\u{1f469}"),
+    ("womans_hat", "\u{1f452}"),
+    ("womens", "\u{1f6ba}"),
+    ("woozy_face", "\u{1f974}"),
+    ("world_map", "\u{1f5fa}\u{fe0f}"),
+    ("worried", "\u{1f61f}"),
+    ("wrench", "\u{1f527}"),
+    ("writing_hand", "\u{270d}\u{fe0f}"),
+    ("x", "\u{274c}"),
+    ("yarn", "\u{1f9f6}"),
+    ("yawning_face", "\u{1f971}"),
+    ("yellow_circle", "\u{1f7e1}"),
+    ("yellow_heart", "\u{1f49b}"),
+    ("yellow_square", "\u{1f7e8}"),
+    ("yen", "\u{1f4b4}"),
+    ("yin_yang", "\u{262f}\u{fe0f}"),
+    ("yo_yo", "\u{1fa80}"),
+    ("yum", "\u{1f60b}"),
+    ("zany_face", "\u{1f92a}"),
+    ("zap", "\u{26a1}"),
+    ("zebra", "\u{1f993}"),
+    ("zero", "0\u{fe0f}\u{20e3}"),
+    ("zipper_mouth_face", "\u{1f910}"),
+    ("zzz", "\u{1f4a4}"),
+];
@@ -5,9 +5,8 @@ pub enum InlineToken {
     Star,
     DoubleStar,
     TripleStar,
-    // \`
-    Quote,
-    DoubleQuote,
+    // `code`, ``co`de``, `raw` is the span as written
+    Code { raw: String, code: String },
     // []
     SquareOpen,
     SquareClose,
@@ -23,12 +22,29 @@ pub enum InlineToken {
     // ==
     Equal,
     DoubleEqual,
+    // line endings inside a paragraph
+    SoftBreak,
+    HardBreak,
+    // &copy; &#169; &#xA9;
+    Entity { raw: String, decoded: String },
+    // $tex$, $$tex$$
+    Math { tex: String, display: bool },
+    // {#id .class} right after `]` or `)`
+    Attrs(String),
+    // [[Page#Heading|label]], ![[file]]
+    WikiLink { raw: String, embed: bool },
+    // :rocket:
+    Emoji { name: String, emoji: &'static str },
+    // <!-- note -->, %% note %%
+    Comment(String),
     Plain(String),
 }
 
 use InlineToken::*;
 
-use crate::md_inline_parser::VecLastMutIfMatch;
+use super::{emoji::emoji, entities::decode_entity, url_len};
+use crate::{md_attributes::Attributes, md_options::ParserOptions};
+
 
 macro_rules! push_to_plain {
     ($tokens:ident, $ch:ident) => {
@@ -41,19 +57,57 @@ macro_rules! push_to_plain {
 }
 
 pub(crate) fn tokenize(data: String) -> Vec<InlineToken> {
+    read_tokens(data, &ParserOptions::default())
+}
+
+fn read_tokens(data: String, options: &ParserOptions) -> Vec<InlineToken> {
     let mut tokens = vec![];
+    let mut chars = data.char_indices();
+    // chars before this byte are already taken by a token
+    let mut skip_until = 0;
 
-    for ch in data.chars() {
+    while let Some((i, ch)) = chars.next() {
+        if i < skip_until {
+            continue;
+        }
         // if escape then skip
         if let Some(Escape) = tokens.last() {
             tokens.pop();
-            push_to_plain!(tokens, ch);
-            continue;
+            if ch == '\n' {
+                // backslash at the end of line
+                tokens.push(HardBreak);
+                continue;
+            } else if ch.is_ascii_punctuation() {
+                push_to_plain!(tokens, ch);
+                continue;
+            }
+            // only ascii punctuations can be escaped,
+            // otherwise backslash stays and `ch` is read as usual.
+            let backslash = '\\';
+            push_to_plain!(tokens, backslash);
         }
         match ch {
             '\\' => {
                 tokens.push(Escape);
             }
+            '\n' => {
+                // two or more trailing spaces make it a hard break,
+                // rest of the trailing spaces are just dropped.
+                let mut spaces = 0;
+                if let Some(Plain(s)) = tokens.last_mut() {
+                    let trimmed = s.trim_end_matches(' ').len();
+                    spaces = s.len() - trimmed;
+                    s.truncate(trimmed);
+                    if s.is_empty() {
+                        tokens.pop();
+                    }
+                }
+                if spaces >= 2 {
+                    tokens.push(HardBreak);
+                } else {
+                    tokens.push(SoftBreak);
+                }
+            }
             '*' => {
                 match tokens.last_mut() {
                     Some(token) if *token == Star => *token = DoubleStar,
@@ -62,11 +116,81 @@ pub(crate) fn tokenize(data: String) -> Vec<InlineToken> {
                 }
             }
             '`' => {
-                match tokens.last_mut() {
-                    Some(token) if *token == Quote => *token = DoubleQuote,
-                    _ => tokens.push(Quote)
+                let (run, span) = code_span(&data[i..]);
+                if let Some((len, code)) = span {
+                    tokens.push(Code {
+                        raw: data[i..i + len].to_string(),
+                        code,
+                    });
+                    skip_until = i + len;
+                } else {
+                    // no closing run, backticks are just text
+                    if let Some(Plain(s)) = tokens.last_mut() {
+                        s.push_str(&data[i..i + run]);
+                    } else {
+                        tokens.push(Plain(data[i..i + run].to_string()));
+                    }
+                    skip_until = i + run;
+                }
+            }
+            '$' if options.math => {
+                if let Some((len, tex, display)) = math_span(&data[i..]) {
+                    tokens.push(Math { tex, display });
+                    skip_until = i + len;
+                } else {
+                    // unclosed `$$` is text as a whole, so its
+                    // second `$` doesn't start another math.
+                    let run = if data[i..].starts_with("$$") { 2 } else { 1 };
+                    if let Some(Plain(s)) = tokens.last_mut() {
+                        s.push_str(&data[i..i + run]);
+                    } else {
+                        tokens.push(Plain(data[i..i + run].to_string()));
+                    }
+                    skip_until = i + run;
                 }
             }
+            '{' if options.attributes
+                && matches!(tokens.last(), Some(SquareClose | CircleClose)) =>
+            {
+                match attrs_span(&data[i..]) {
+                    Some(len) => {
+                        tokens.push(Attrs(data[i..i + len].to_string()));
+                        skip_until = i + len;
+                    }
+                    None => push_to_plain!(tokens, ch),
+                }
+            }
+            '[' if options.wiki_links && wiki_span(&data[i..]).is_some() => {
+                let len = wiki_span(&data[i..]).unwrap_or_default();
+                // `!` right before makes it an embed
+                let mut embed = false;
+                if let Some(Plain(s)) = tokens.last_mut() {
+                    if s.ends_with('!') {
+                        s.pop();
+                        embed = true;
+                        if s.is_empty() {
+                            tokens.pop();
+                        }
+                    }
+                }
+                tokens.push(WikiLink {
+                    raw: data[i + 2..i + len - 2].to_string(),
+                    embed,
+                });
+                skip_until = i + len;
+            }
+            ':' if options.emoji && !in_url(&data[..i]) => match emoji_span(&data[i..]) {
+                Some((len, name, emoji)) => {
+                    tokens.push(Emoji { name, emoji });
+                    skip_until = i + len;
+                }
+                None => push_to_plain!(tokens, ch),
+            },
+            '<' | '%' if options.comments && comment_span(&data[i..]).is_some() => {
+                let len = comment_span(&data[i..]).unwrap_or_default();
+                tokens.push(Comment(data[i..i + len].to_string()));
+                skip_until = i + len;
+            }
             '[' => {
                 tokens.push(SquareOpen);
             }
@@ -97,29 +221,263 @@ pub(crate) fn tokenize(data: String) -> Vec<InlineToken> {
                     _ => tokens.push(Equal)
                 }
             }
+            '&' => {
+                if let Some((len, decoded)) = decode_entity(&data[i..]) {
+                    tokens.push(Entity {
+                        raw: data[i..i + len].to_string(),
+                        decoded,
+                    });
+                    // references are all ascii, so bytes are chars
+                    chars.nth(len - 2);
+                } else {
+                    push_to_plain!(tokens, ch);
+                }
+            }
             ch => {
                 push_to_plain!(tokens, ch);
             }
         }
     }
 
+    // backslash at the very end has nothing to escape
+    if let Some(Escape) = tokens.last() {
+        tokens.pop();
+        let backslash = '\\';
+        push_to_plain!(tokens, backslash);
+    }
+
     // NOTE: returning
     tokens
 }
 
+///
+/// tokens of disabled extensions are turned back into `Plain`
+///
+pub(crate) fn tokenize_with(data: String, options: &ParserOptions) -> Vec<InlineToken> {
+    let mut tokens = vec![];
+    // without footnotes `[^` is a bracket and a carat
+    let read = read_tokens(data, options).into_iter().flat_map(|token| match token {
+        FootnoteOpen if !options.footnotes => vec![SquareOpen, Carat],
+        token => vec![token],
+    });
+    for token in read {
+        let enabled = match token {
+            Equal | DoubleEqual => options.highlight,
+            Strike | Carat => options.sub_sup,
+            DoubleStrike => options.strikethrough,
+            Plain(_) => false,
+            _ => true,
+        };
+        if enabled {
+            tokens.push(token);
+        } else if let Some(Plain(s)) = tokens.last_mut() {
+            s.push_str(&token.to_string());
+        } else {
+            tokens.push(Plain(token.to_string()));
+        }
+    }
+    tokens
+}
+
+///
+/// `data` starts with a backtick run, which can only be closed by
+/// another run of exactly the same length.
+///
+/// returns length of the opening run, and if closed, byte length
+/// of the whole code span with its content.
+///
+fn code_span(data: &str) -> (usize, Option<(usize, String)>) {
+    let bytes = data.as_bytes();
+    let run = bytes.iter().take_while(|&&b| b == b'`').count();
+
+    let mut pos = run;
+    while pos < bytes.len() {
+        if bytes[pos] != b'`' {
+            pos += 1;
+            continue;
+        }
+        let close = bytes[pos..].iter().take_while(|&&b| b == b'`').count();
+        if close == run {
+            // line endings are read as spaces
+            let mut code = data[run..pos].replace('\n', " ");
+            // one space is stripped from both sides, if
+            // both are there and code isn't all spaces.
+            if code.len() > 1
+                && code.starts_with(' ')
+                && code.ends_with(' ')
+                && !code.bytes().all(|b| b == b' ')
+            {
+                code = code[1..code.len() - 1].to_string();
+            }
+            return (run, Some((pos + close, code)));
+        }
+        pos += close;
+    }
+    (run, None)
+}
+
+/// byte length of `<!-- comment -->` or `%% comment %%` at the start of `data`
+fn comment_span(data: &str) -> Option<usize> {
+    let (open, close) = if data.starts_with("<!--") {
+        ("<!--", "-->")
+    } else if data.starts_with("%%") {
+        ("%%", "%%")
+    } else {
+        return None;
+    };
+    let end = data[open.len()..].find(close)?;
+    Some(open.len() + end + close.len())
+}
+
+///
+/// `data` starts with `$` or `$$`, math is read like pandoc does.
+///
+/// `$$` is closed by the next `$$`. with a single `$`, there can't
+/// be a space right after the opening one, nor right before the
+/// closing one, and the closing one can't be followed by a digit,
+/// so `$5 and $6` stays text. `\$` inside doesn't close.
+///
+/// returns byte length of the whole math, its tex and if it's display.
+///
+fn math_span(data: &str) -> Option<(usize, String, bool)> {
+    let bytes = data.as_bytes();
+    if let Some(rest) = data.strip_prefix("$$") {
+        let tex = &rest[..rest.find("$$")?];
+        if tex.trim().is_empty() {
+            return None;
+        }
+        return Some((tex.len() + 4, tex.to_string(), true));
+    }
+
+    if bytes.get(1)?.is_ascii_whitespace() {
+        return None;
+    }
+    let mut pos = 1;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\\' => pos += 2,
+            b'$' => {
+                let closes = !bytes[pos - 1].is_ascii_whitespace()
+                    && !bytes.get(pos + 1).is_some_and(u8::is_ascii_digit);
+                if closes {
+                    return Some((pos + 1, data[1..pos].to_string(), false));
+                }
+                pos += 1;
+            }
+            _ => pos += 1,
+        }
+    }
+    None
+}
+
+///
+/// byte length of `[[...]]` at the start of `data`, it has
+/// to be on one line without any other brackets inside.
+///
+fn wiki_span(data: &str) -> Option<usize> {
+    let inner = data.strip_prefix("[[")?;
+    let end = inner.find("]]")?;
+    let raw = &inner[..end];
+    let valid = !raw.trim().is_empty() && !raw.contains(['[', ']', '\n']);
+    valid.then_some(end + 4)
+}
+
+///
+/// `:shortcode:` at the start of `data`, if it's a known one.
+///
+/// returns byte length of it, the shortcode and its emoji.
+///
+fn emoji_span(data: &str) -> Option<(usize, String, &'static str)> {
+    let inner = data.strip_prefix(':')?;
+    let end = inner
+        .bytes()
+        .position(|b| !(b.is_ascii_alphanumeric() || matches!(b, b'_' | b'+' | b'-')))?;
+    if end == 0 || inner.as_bytes()[end] != b':' {
+        return None;
+    }
+    let name = &inner[..end];
+    emoji(name).map(|emoji| (end + 2, name.to_string(), emoji))
+}
+
+/// `before` ends inside a bare url, like `https://a.b/:x:`
+fn in_url(before: &str) -> bool {
+    let word = before.rsplit(char::is_whitespace).next().unwrap_or_default();
+    url_len(word).is_some()
+}
+
+///
+/// byte length of `{...}` at the start of `data`,
+/// if it holds nothing but attributes.
+///
+fn attrs_span(data: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, ch) in data.char_indices() {
+        match (quote, ch) {
+            (_, '\n') => return None,
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), ch) if ch == q => quote = None,
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '}') => {
+                return Attributes::from_braces(&data[..=i]).map(|_| i + 1);
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+fn code(raw: &str, code: &str) -> InlineToken {
+    Code {
+        raw: raw.to_string(),
+        code: code.to_string(),
+    }
+}
+
 #[test]
 fn test_inline_tokens() {
     assert_eq!(
         tokenize("`code **bold**`".to_string()),
+        vec![code("`code **bold**`", "code **bold**")]
+    );
+}
+
+#[test]
+fn test_inline_code_span() {
+    // only a run of same length closes
+    assert_eq!(
+        tokenize("``a`b``` c``".to_string()),
+        vec![code("``a`b``` c``", "a`b``` c")]
+    );
+    // and it's written back as it was
+    assert_eq!(tokenize("``a`b``".to_string())[0].to_string(), "``a`b``");
+    assert_eq!(
+        tokenize("``` `` ``".to_string()),
+        vec![Plain("``` ".to_string()), code("`` ``", " ")]
+    );
+    // one space stripped from both ends
+    assert_eq!(
+        tokenize("`` `a` `` ` b ` `  `".to_string()),
         vec![
-            Quote,
-            Plain("code ".to_string()),
-            DoubleStar,
-            Plain("bold".to_string()),
-            DoubleStar,
-            Quote,
+            code("`` `a` ``", "`a`"),
+            Plain(" ".to_string()),
+            code("` b `", "b"),
+            Plain(" ".to_string()),
+            code("`  `", "  "),
         ]
     );
+    // no escapes or entities inside, line endings are spaces
+    assert_eq!(
+        tokenize("`\\* &amp;\nnext`*".to_string()),
+        vec![code("`\\* &amp;\nnext`", "\\* &amp; next"), Star]
+    );
+    // unclosed run is text
+    assert_eq!(
+        tokenize("a ``b`".to_string()),
+        vec![Plain("a ``b`".to_string())]
+    );
 }
 
 #[test]
@@ -171,6 +529,18 @@ fn test_inline_escape() {
     );
 }
 
+#[test]
+fn test_inline_escape_only_punctuation() {
+    assert_eq!(
+        tokenize("\\a \\_ \\# \\\\*".to_string()),
+        vec![Plain("\\a _ # \\".to_string()), Star]
+    );
+    assert_eq!(
+        tokenize("\\\u{e9} end\\".to_string()),
+        vec![Plain("\\\u{e9} end\\".to_string())]
+    );
+}
+
 #[test]
 fn test_inline_footnote() {
     assert_eq!(
@@ -188,27 +558,228 @@ fn test_inline_footnote() {
 
 }
 
+#[test]
+fn test_inline_breaks() {
+    assert_eq!(
+        tokenize("soft \nhard  \nslash\\\nend".to_string()),
+        vec![
+            Plain("soft".to_string()),
+            SoftBreak,
+            Plain("hard".to_string()),
+            HardBreak,
+            Plain("slash".to_string()),
+            HardBreak,
+            Plain("end".to_string()),
+        ]
+    );
+    assert_eq!(
+        tokenize("**bold\ntext**".to_string()),
+        vec![
+            DoubleStar,
+            Plain("bold".to_string()),
+            SoftBreak,
+            Plain("text".to_string()),
+            DoubleStar
+        ]
+    );
+}
+
+#[test]
+fn test_inline_entity() {
+    assert_eq!(
+        tokenize("&copy; &amp \\&lt;".to_string()),
+        vec![
+            Entity {
+                raw: "&copy;".to_string(),
+                decoded: "\u{a9}".to_string()
+            },
+            Plain(" &amp &lt;".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_inline_disabled_extensions() {
+    let options = ParserOptions {
+        sub_sup: false,
+        ..ParserOptions::default()
+    };
+    assert_eq!(
+        tokenize_with("~a~ ~~b~~ ^c^".to_string(), &options),
+        vec![
+            Plain("~a~ ".to_string()),
+            DoubleStrike,
+            Plain("b".to_string()),
+            DoubleStrike,
+            Plain(" ^c^".to_string()),
+        ]
+    );
+    assert_eq!(
+        tokenize_with("==a== *b*".to_string(), &ParserOptions::commonmark()),
+        vec![Plain("==a== ".to_string()), Star, Plain("b".to_string()), Star]
+    );
+    assert_eq!(
+        tokenize_with("[^1]".to_string(), &ParserOptions::commonmark()),
+        vec![SquareOpen, Plain("^1".to_string()), SquareClose]
+    );
+}
+
+#[test]
+fn test_inline_math() {
+    let math = |tex: &str, display| Math {
+        tex: tex.to_string(),
+        display,
+    };
+    // nothing inside is tokenized
+    assert_eq!(
+        tokenize("$a*b*c_1$ and $$\\sum_{i=1}^n x_i$$".to_string()),
+        vec![
+            math("a*b*c_1", false),
+            Plain(" and ".to_string()),
+            math("\\sum_{i=1}^n x_i", true),
+        ]
+    );
+    // space after opening, or before closing, or digit after closing
+    assert_eq!(
+        tokenize("$ a$ $a $ $5 and $6".to_string()),
+        vec![Plain("$ a$ $a $ $5 and $6".to_string())]
+    );
+    // escaped dollar inside, and outside
+    assert_eq!(
+        tokenize("$\\$x$ \\$y$".to_string()),
+        vec![math("\\$x", false), Plain(" $y$".to_string())]
+    );
+    assert_eq!(
+        tokenize("$$ $$ *a*".to_string()),
+        vec![Plain("$$ $$ ".to_string()), Star, Plain("a".to_string()), Star]
+    );
+    // off, and it's all text
+    assert_eq!(
+        tokenize_with("$*a*$".to_string(), &ParserOptions::commonmark()),
+        vec![Plain("$".to_string()), Star, Plain("a".to_string()), Star, Plain("$".to_string())]
+    );
+}
+
+#[test]
+fn test_inline_attrs() {
+    assert_eq!(
+        tokenize("[a]{.x title=\"}\"} (b){#y} c{.z} [d]{not attrs}".to_string()),
+        vec![
+            SquareOpen,
+            Plain("a".to_string()),
+            SquareClose,
+            Attrs("{.x title=\"}\"}".to_string()),
+            Plain(" ".to_string()),
+            CircleOpen,
+            Plain("b".to_string()),
+            CircleClose,
+            Attrs("{#y}".to_string()),
+            Plain(" c{.z} ".to_string()),
+            SquareOpen,
+            Plain("d".to_string()),
+            SquareClose,
+            Plain("{not attrs}".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_inline_wiki_links() {
+    assert_eq!(
+        tokenize("see [[Page#Head|label]] ![[img.png]] [[a\nb]]".to_string()),
+        vec![
+            Plain("see ".to_string()),
+            WikiLink {
+                raw: "Page#Head|label".to_string(),
+                embed: false
+            },
+            Plain(" ".to_string()),
+            WikiLink {
+                raw: "img.png".to_string(),
+                embed: true
+            },
+            Plain(" ".to_string()),
+            SquareOpen,
+            SquareOpen,
+            Plain("a".to_string()),
+            SoftBreak,
+            Plain("b".to_string()),
+            SquareClose,
+            SquareClose,
+        ]
+    );
+}
+
+#[test]
+fn test_inline_emoji() {
+    assert_eq!(
+        tokenize(":+1: ok:rocket: :nope: 10:30: `:tada:` \\:tada:".to_string()),
+        vec![
+            Emoji {
+                name: "+1".to_string(),
+                emoji: "\u{1f44d}"
+            },
+            Plain(" ok".to_string()),
+            Emoji {
+                name: "rocket".to_string(),
+                emoji: "\u{1f680}"
+            },
+            Plain(" :nope: 10:30: ".to_string()),
+            code("`:tada:`", ":tada:"),
+            Plain(" :tada:".to_string()),
+        ]
+    );
+    // bare urls are left alone
+    assert_eq!(
+        tokenize("https://a.b/:tada: www.a.b/:tada:".to_string()),
+        vec![Plain("https://a.b/:tada: www.a.b/:tada:".to_string())]
+    );
+}
+
+#[test]
+fn test_inline_comment() {
+    assert_eq!(
+        tokenize("*a <!-- * --> b* 50% %%note%% <!-- open".to_string()),
+        vec![
+            Star,
+            Plain("a ".to_string()),
+            Comment("<!-- * -->".to_string()),
+            Plain(" b".to_string()),
+            Star,
+            Plain(" 50% ".to_string()),
+            Comment("%%note%%".to_string()),
+            Plain(" <!-- open".to_string()),
+        ]
+    );
+}
+
 // to_string impl
-impl InlineToken {
-    pub fn to_string(&self) -> String {
-        match self {
-            Escape => "\\".to_string(),
-            Star => "*".to_string(),
-            DoubleStar => "**".to_string(),
-            TripleStar => "***".to_string(),
-            Quote => "`".to_string(),
-            DoubleQuote => "``".to_string(),
-            SquareOpen => "[".to_string(),
-            SquareClose => "]".to_string(),
-            CircleOpen => "(".to_string(),
-            CircleClose => ")".to_string(),
-            Carat => "^".to_string(),
-            FootnoteOpen => "[^".to_string(),
-            Strike => "~".to_string(),
-            DoubleStrike => "~~".to_string(),
-            Equal => "=".to_string(),
-            DoubleEqual => "==".to_string(),
-            Plain(s) => s.to_string(),
-        }
+impl std::fmt::Display for InlineToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Escape => "\\",
+            Star => "*",
+            DoubleStar => "**",
+            TripleStar => "***",
+            SquareOpen => "[",
+            SquareClose => "]",
+            CircleOpen => "(",
+            CircleClose => ")",
+            Carat => "^",
+            FootnoteOpen => "[^",
+            Strike => "~",
+            DoubleStrike => "~~",
+            Equal => "=",
+            DoubleEqual => "==",
+            SoftBreak | HardBreak => "\n",
+            Entity { raw, .. } | Code { raw, .. } | Attrs(raw) | Comment(raw) => raw,
+            WikiLink { raw, embed: false } => return write!(f, "[[{}]]", raw),
+            WikiLink { raw, embed: true } => return write!(f, "![[{}]]", raw),
+            Math { tex, display: false } => return write!(f, "${}$", tex),
+            Math { tex, display: true } => return write!(f, "$${}$$", tex),
+            Emoji { name, .. } => return write!(f, ":{}:", name),
+            Plain(s) => s,
+        };
+        f.write_str(s)
     }
 }
@@ -1,11 +1,11 @@
 use std::slice::Iter;
 
-use crate::md_inline_parser::inline_tokens;
+use crate::{md_attributes::Attributes, md_inline_parser::inline_tokens};
 
-use super::{inline_tokens::InlineToken, md_string::MdString, VecLastMutIfMatch};
+use super::{inline_tokens::InlineToken, md_string::MdString, references::Reference};
 
 #[derive(Debug, PartialEq)]
-pub(crate) enum MdInline {
+pub enum MdInline {
     Bold(MdString),
     Italic(MdString),
     BoldItalic(MdString),
@@ -17,84 +17,420 @@ pub(crate) enum MdInline {
     LinkText(MdString),
     LinkUrl(MdString),
     Footnote(MdString),
+    /// `![alt](url "title")` inside text
+    Image {
+        alt: MdString,
+        url: String,
+        title: Option<String>,
+        /// `=WxH` after the url, as `width` and `height`
+        attrs: Attributes,
+    },
+    /// `[[target#anchor|label]]`, or an embed with `![[target]]`
+    WikiLink {
+        target: String,
+        anchor: Option<String>,
+        label: Option<String>,
+        embed: bool,
+        /// `|200` or `|200x100` of an embed, as `width` and `height`
+        attrs: Attributes,
+    },
+    /// `{#id .class}` for the link or `[span]` right before it
+    Attributes(Attributes),
+    /// `$tex$`, kept as it was written
+    Math(String),
+    /// `$$tex$$`
+    DisplayMath(String),
+    /// `:name:` shortcode and its emoji
+    Emoji { name: String, emoji: String },
+    /// abbreviation defined with `*[text]: title`
+    Abbr { text: String, title: String },
+    /// `@user`, `#123`, `org/repo#45` or `#tag`
+    Reference(Reference),
+    /// `<!-- note -->` or `%% note %%`, as it was written
+    Comment(String),
+    /// line ending inside a paragraph, renders as a space
+    SoftBreak,
+    /// line ending with two spaces or a backslash
+    HardBreak,
     InlineString(String),
 }
 
 use MdInline::*;
 
+///
+/// inlines from `tokens` until the `until` token, or the end.
+///
+/// returns if `until` was found, when it's not the caller takes
+/// its opening token as text.
+///
 pub fn from_tokens_to_mdinline(
     tokens: &mut Iter<'_, InlineToken>,
     md_string: &mut MdString,
     until: Option<InlineToken>,
     escape: bool,
-) {
-    let token = tokens.next();
-    // token == matters, here don't move it
-    if token.is_none() || until.as_ref() == token {
-        return;
-    }
+) -> bool {
+    let closers: Vec<&InlineToken> = until.iter().collect();
+    let ahead = Ahead::new(tokens.as_slice());
+    parse_inlines(tokens, md_string, &closers, escape, &ahead)
+}
+
+///
+/// what's ahead in the tokens, found in one pass before parsing,
+/// so an opener which is never closed doesn't look through all the
+/// rest of the tokens again, which would be slow for a lot of them.
+///
+struct Ahead<'a> {
+    /// number of tokens, index of a token is this minus what's left from it on
+    len: usize,
+    /// `^super^` and `~sub~` at this index are closed by the same token like
+    /// in pandoc, with something in between but no unescaped space or line break
+    sub_sup: Vec<bool>,
+    /// last index of each token which closes spans
+    last: Vec<(&'a InlineToken, usize)>,
+}
 
-    let token = token.unwrap();
+impl<'a> Ahead<'a> {
+    fn new(tokens: &'a [InlineToken]) -> Ahead<'a> {
+        let mut sub_sup = vec![false; tokens.len()];
+        let mut last: Vec<(&InlineToken, usize)> = vec![];
+        // next `^` and `~` in the same run of text
+        let (mut carat, mut strike) = (None, None);
+        for (i, token) in tokens.iter().enumerate().rev() {
+            match token {
+                InlineToken::SoftBreak | InlineToken::HardBreak => (carat, strike) = (None, None),
+                InlineToken::Plain(s) if has_unescaped_space(s) => (carat, strike) = (None, None),
+                InlineToken::Carat => {
+                    sub_sup[i] = carat.is_some_and(|next| next > i + 1);
+                    carat = Some(i);
+                }
+                InlineToken::Strike => {
+                    sub_sup[i] = strike.is_some_and(|next| next > i + 1);
+                    strike = Some(i);
+                }
+                _ => (),
+            }
+            let closes_spans = matches!(
+                token,
+                InlineToken::Star
+                    | InlineToken::DoubleStar
+                    | InlineToken::TripleStar
+                    | InlineToken::DoubleStrike
+                    | InlineToken::DoubleEqual
+                    | InlineToken::Carat
+                    | InlineToken::Strike
+                    | InlineToken::SquareClose
+                    | InlineToken::CircleClose
+            );
+            if closes_spans && !last.iter().any(|(seen, _)| *seen == token) {
+                last.push((token, i));
+            }
+        }
+        Ahead {
+            len: tokens.len(),
+            sub_sup,
+            last,
+        }
+    }
 
-    macro_rules! enclosed_matches {
-        ($till:expr, $make:expr) => {{
-            let mut i_md_string = MdString::new();
-            from_tokens_to_mdinline(tokens, &mut i_md_string, Some($till), false);
-            md_string.push($make(i_md_string));
-        }};
+    /// index of the token `tokens` gives next
+    fn index(&self, tokens: &Iter<'_, InlineToken>) -> usize {
+        self.len - tokens.len()
     }
 
-    macro_rules! escaped_enclosed_matches {
-        ($till:expr, $make:expr) => {{
-            let mut i_md_string = MdString::new();
-            from_tokens_to_mdinline(tokens, &mut i_md_string, Some($till), true);
-            // TODO: merge i_md_string
-            md_string.push($make(i_md_string));
-        }};
+    /// `till` comes somewhere after the token at `index`
+    fn closes(&self, till: &InlineToken, index: usize) -> bool {
+        self.last
+            .iter()
+            .any(|(token, last)| *token == till && *last > index)
     }
+}
 
-    // Inits go above this.
-    // escaping
-    if escape {
-        md_string.push(MdInline::InlineString(token.to_string()));
-        from_tokens_to_mdinline(tokens, md_string, until, true);
-        return;
+fn has_unescaped_space(s: &str) -> bool {
+    let mut escaped = false;
+    for ch in s.chars() {
+        if ch.is_ascii_whitespace() && !escaped {
+            return true;
+        }
+        escaped = ch == '\\';
     }
+    false
+}
+
+/// `\ ` in super and sub scripts is a non-breaking space
+fn escaped_spaces(md_string: MdString) -> MdString {
+    MdString::from_vec(
+        md_string
+            .into_vec()
+            .into_iter()
+            .map(|inline| match inline {
+                InlineString(s) => InlineString(s.replace("\\ ", "\u{a0}")),
+                inline => inline,
+            })
+            .collect(),
+    )
+}
 
-    match token {
-        InlineToken::Escape => (),
-        InlineToken::Star => enclosed_matches!(InlineToken::Star, MdInline::Italic),
-        InlineToken::DoubleStar => enclosed_matches!(InlineToken::DoubleStar, MdInline::Bold),
-        InlineToken::TripleStar => enclosed_matches!(InlineToken::TripleStar, MdInline::BoldItalic),
-        InlineToken::DoubleStrike => enclosed_matches!(InlineToken::DoubleStrike, MdInline::Strike),
-        InlineToken::DoubleEqual => {
-            enclosed_matches!(InlineToken::DoubleEqual, MdInline::Highlight)
+///
+/// `closers` are the tokens which close the spans we are in, innermost
+/// last. a closer of an outer span ends this one unclosed, and is left
+/// for that span to take.
+///
+fn parse_inlines(
+    tokens: &mut Iter<'_, InlineToken>,
+    md_string: &mut MdString,
+    closers: &[&InlineToken],
+    escape: bool,
+    ahead: &Ahead<'_>,
+) -> bool {
+    // loop instead of recursing for every token,
+    // as a paragraph can have a lot of them.
+    while let Some(token) = tokens.clone().next() {
+        let index = ahead.index(tokens);
+        // token == matters, here don't move it
+        if closers.last() == Some(&token) {
+            tokens.next();
+            return true;
         }
-        InlineToken::Strike => enclosed_matches!(InlineToken::Strike, MdInline::Super),
-        InlineToken::Carat => enclosed_matches!(InlineToken::Carat, MdInline::Sub),
-        InlineToken::Quote => escaped_enclosed_matches!(InlineToken::Quote, MdInline::Code),
-        InlineToken::DoubleQuote => {
-            escaped_enclosed_matches!(InlineToken::DoubleQuote, MdInline::Code)
+        // escaped text like urls goes on till its own closer
+        if !escape && closers.contains(&token) {
+            return false;
         }
-        InlineToken::SquareOpen => enclosed_matches!(InlineToken::SquareClose, MdInline::LinkText),
-        InlineToken::SquareClose => (),
-        InlineToken::CircleOpen => {
-            escaped_enclosed_matches!(InlineToken::SquareClose, MdInline::LinkUrl)
+        tokens.next();
+
+        macro_rules! enclosed_matches {
+            ($till:expr, $make:expr) => {{
+                let till = $till;
+                let mut i_md_string = MdString::new();
+                // with no closer ahead at all, there's nothing to look for
+                let closed = ahead.closes(&till, index) && {
+                    let mut inner_closers = closers.to_vec();
+                    inner_closers.push(&till);
+                    parse_inlines(tokens, &mut i_md_string, &inner_closers, false, ahead)
+                };
+                if closed {
+                    md_string.push($make(i_md_string));
+                } else {
+                    // never closed, so the opener is just text
+                    md_string.push_str(&token.to_string());
+                    for inline in i_md_string.into_vec() {
+                        match inline {
+                            InlineString(s) => md_string.push_str(&s),
+                            inline => md_string.push(inline),
+                        }
+                    }
+                }
+            }};
         }
-        InlineToken::CircleClose => (),
-        InlineToken::FootnoteOpen => {
-            escaped_enclosed_matches!(InlineToken::SquareClose, MdInline::Footnote)
+
+        macro_rules! escaped_enclosed_matches {
+            ($till:expr, $make:expr) => {{
+                let closed = ahead.closes(&$till, index);
+                if closed {
+                    let mut i_md_string = MdString::new();
+                    parse_inlines(tokens, &mut i_md_string, &[&$till], true, ahead);
+                    md_string.push($make(i_md_string));
+                } else {
+                    // never closed, the opener is text and the rest is read as usual
+                    md_string.push_str(&token.to_string());
+                }
+                closed
+            }};
         }
-        InlineToken::Equal => md_string.push(InlineString(String::from('='))),
-        InlineToken::Plain(f) => {
-            if let Some(InlineString(s)) = md_string.last_mut() {
-                s.push_str(f.as_str())
-            } else {
-                md_string.push(InlineString(f.to_owned()));
+
+        // Inits go above this.
+        // escaping
+        if escape {
+            match token {
+                // the renderer escapes it again
+                InlineToken::Entity { decoded, .. } => md_string.push_str(decoded),
+                token => md_string.push_str(&token.to_string()),
             }
+            continue;
+        }
+
+        match token {
+            InlineToken::Escape => (),
+            InlineToken::Star => enclosed_matches!(InlineToken::Star, MdInline::Italic),
+            InlineToken::DoubleStar => enclosed_matches!(InlineToken::DoubleStar, MdInline::Bold),
+            InlineToken::TripleStar => {
+                enclosed_matches!(InlineToken::TripleStar, MdInline::BoldItalic)
+            }
+            InlineToken::DoubleStrike => {
+                enclosed_matches!(InlineToken::DoubleStrike, MdInline::Strike)
+            }
+            InlineToken::DoubleEqual => {
+                enclosed_matches!(InlineToken::DoubleEqual, MdInline::Highlight)
+            }
+            // ^super^ and ~sub~ like in pandoc
+            InlineToken::Carat | InlineToken::Strike if !ahead.sub_sup[index] => {
+                md_string.push_str(&token.to_string())
+            }
+            InlineToken::Carat => {
+                enclosed_matches!(InlineToken::Carat, |s| MdInline::Super(escaped_spaces(s)))
+            }
+            InlineToken::Strike => {
+                enclosed_matches!(InlineToken::Strike, |s| MdInline::Sub(escaped_spaces(s)))
+            }
+            InlineToken::Code { code, .. } => md_string.push(MdInline::Code(MdString::from_vec(vec![
+                InlineString(code.to_owned()),
+            ]))),
+            InlineToken::WikiLink { raw, embed } => {
+                // label after `|`, anchor after `#`
+                let (link, mut label) = match raw.split_once('|') {
+                    Some((link, label)) => (link, Some(label.trim().to_string())),
+                    None => (raw.as_str(), None),
+                };
+                // a number there is the size of an embed, not its label
+                let size = label.as_deref().filter(|_| *embed).and_then(embed_size);
+                if size.is_some() {
+                    label = None;
+                }
+                let (target, anchor) = match link.split_once('#') {
+                    Some((target, anchor)) => (target, Some(anchor.trim().to_string())),
+                    None => (link, None),
+                };
+                md_string.push(WikiLink {
+                    target: target.trim().to_string(),
+                    anchor,
+                    label,
+                    embed: *embed,
+                    attrs: size.unwrap_or_default(),
+                });
+            }
+            InlineToken::Attrs(raw) => md_string.push(MdInline::Attributes(
+                Attributes::from_braces(raw).unwrap_or_default(),
+            )),
+            InlineToken::Math { tex, display: false } => md_string.push(Math(tex.to_owned())),
+            InlineToken::Math { tex, display: true } => {
+                md_string.push(DisplayMath(tex.to_owned()))
+            }
+            InlineToken::Emoji { name, emoji } => md_string.push(Emoji {
+                name: name.to_owned(),
+                emoji: emoji.to_string(),
+            }),
+            InlineToken::Comment(raw) => md_string.push(Comment(raw.to_owned())),
+            InlineToken::SquareOpen => {
+                enclosed_matches!(InlineToken::SquareClose, MdInline::LinkText)
+            }
+            InlineToken::CircleOpen => {
+                if escaped_enclosed_matches!(InlineToken::CircleClose, MdInline::LinkUrl) {
+                    image_from_link(md_string);
+                }
+            }
+            // nothing open to close, just text
+            InlineToken::SquareClose | InlineToken::CircleClose => {
+                md_string.push_str(&token.to_string())
+            }
+            InlineToken::FootnoteOpen => {
+                escaped_enclosed_matches!(InlineToken::SquareClose, MdInline::Footnote);
+            }
+            // only `==` highlights, single one is just text
+            InlineToken::Equal => md_string.push_str("="),
+            InlineToken::SoftBreak => md_string.push(SoftBreak),
+            InlineToken::HardBreak => md_string.push(HardBreak),
+            InlineToken::Entity { decoded, .. } => md_string.push_str(decoded),
+            InlineToken::Plain(f) => md_string.push_str(f),
+        }
+    }
+    false
+}
+
+///
+/// `!` + `LinkText` + `LinkUrl` at the end of `md_string`
+/// is an image, which replaces them.
+///
+fn image_from_link(md_string: &mut MdString) {
+    let mut last = md_string.iter().rev();
+    let (Some(LinkUrl(url)), Some(LinkText(_)), Some(InlineString(s))) =
+        (last.next(), last.next(), last.next())
+    else {
+        return;
+    };
+    if !s.ends_with('!') {
+        return;
+    }
+    let url = url
+        .iter()
+        .filter_map(|inline| match inline {
+            InlineString(s) => Some(s.as_str()),
+            _ => None,
+        })
+        .collect::<String>();
+    let Some((url, title, attrs)) = split_destination(&url) else {
+        return;
+    };
+    md_string.pop();
+    let Some(LinkText(alt)) = md_string.pop() else {
+        return;
+    };
+    if let Some(InlineString(s)) = md_string.last_mut() {
+        s.pop();
+        if s.is_empty() {
+            md_string.pop();
         }
     }
-    from_tokens_to_mdinline(tokens, md_string, until, false);
+    md_string.push(Image {
+        alt,
+        url,
+        title,
+        attrs,
+    });
+}
+
+///
+/// url of a link or image, with the optional `"title"` and `=WxH`
+/// size after it. `None` if there's anything else in `dest`.
+///
+/// size is given as `width` and `height` attributes,
+/// either of them can be left out, `=100x` or `=x50`.
+///
+pub(crate) fn split_destination(dest: &str) -> Option<(String, Option<String>, Attributes)> {
+    let dest = dest.trim();
+    let end = dest.find(char::is_whitespace).unwrap_or(dest.len());
+    let (url, mut rest) = (&dest[..end], dest[end..].trim_start());
+    if url.is_empty() || url.contains(['(', ')']) {
+        return None;
+    }
+
+    let mut title = None;
+    if let Some(q) = rest.chars().next().filter(|q| matches!(q, '"' | '\'')) {
+        let close = rest[1..].find(q)? + 1;
+        title = Some(rest[1..close].to_string());
+        rest = rest[close + 1..].trim_start();
+    }
+
+    let mut attrs = Attributes::default();
+    if let Some(size) = rest.strip_prefix('=') {
+        let (width, height) = size.split_once('x')?;
+        if width.is_empty() && height.is_empty() {
+            return None;
+        }
+        for (key, value) in [("width", width), ("height", height)] {
+            if !value.is_empty() {
+                attrs.pairs.push((key.to_string(), value.to_string()));
+            }
+        }
+        rest = "";
+    }
+    rest.is_empty().then(|| (url.to_string(), title, attrs))
+}
+
+/// `200` or `200x100` after the `|` of an embed, as `width` and `height`
+fn embed_size(label: &str) -> Option<Attributes> {
+    let (width, height) = match label.split_once('x') {
+        Some((width, height)) => (width, Some(height)),
+        None => (label, None),
+    };
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !is_number(width) || height.is_some_and(|height| !is_number(height)) {
+        return None;
+    }
+    let mut attrs = Attributes::default();
+    attrs.pairs.push(("width".to_string(), width.to_string()));
+    if let Some(height) = height {
+        attrs.pairs.push(("height".to_string(), height.to_string()));
+    }
+    Some(attrs)
 }
 
 #[test]
@@ -124,13 +460,9 @@ fn test_mdline_string_modifiers() {
 
     let result = MdString::from_vec(vec![
         MdInline::InlineString("Hello *".to_string()),
-        MdInline::Italic(MdString::from_vec(vec![
-            InlineString("dkjf".to_string()),
-            Bold(MdString::from_vec(vec![
-                InlineString("world*".to_string()),
-                Italic(MdString::from_vec(vec![InlineString(" !".to_string())])),
-            ])),
-        ])),
+        // `**` isn't closed before the italic is, so it's text
+        MdInline::Italic(MdString::from_vec(vec![InlineString("dkjf**world*".to_string())])),
+        MdInline::InlineString(" !".to_string()),
     ]);
 
     assert_eq!(md_string, result);
@@ -196,3 +528,146 @@ fn test_mdline_footnote() {
     ]))]);
     assert_eq!(md_string, result);
 }
+
+#[test]
+fn test_mdline_spans_lines() {
+    let mut md_string = MdString::new();
+    from_tokens_to_mdinline(
+        &mut inline_tokens::tokenize("**bold\ntext** end  \nnext".to_string()).iter(),
+        &mut md_string,
+        None,
+        false,
+    );
+
+    let result = MdString::from_vec(vec![
+        Bold(MdString::from_vec(vec![
+            InlineString("bold".to_string()),
+            SoftBreak,
+            InlineString("text".to_string()),
+        ])),
+        InlineString(" end".to_string()),
+        HardBreak,
+        InlineString("next".to_string()),
+    ]);
+    assert_eq!(md_string, result);
+}
+
+#[test]
+fn test_mdline_unclosed() {
+    let md_string = MdString::from_string("price 2*3\nsecond **line** ==a ~~b".to_string());
+    assert_eq!(
+        md_string,
+        MdString::from_vec(vec![
+            InlineString("price 2*3".to_string()),
+            SoftBreak,
+            InlineString("second ".to_string()),
+            Bold(MdString::from_vec(vec![InlineString("line".to_string())])),
+            InlineString(" ==a ~~b".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn test_mdline_many_unclosed() {
+    let data = "a ~b [c [^d (e ".repeat(20_000);
+    let md_string = MdString::from_string(data.clone());
+    assert_eq!(md_string, MdString::from_vec(vec![InlineString(data)]));
+}
+
+#[test]
+fn test_mdline_entity() {
+    let mut md_string = MdString::new();
+    from_tokens_to_mdinline(
+        &mut inline_tokens::tokenize("&copy; &#169; `&copy;`".to_string()).iter(),
+        &mut md_string,
+        None,
+        false,
+    );
+
+    let result = MdString::from_vec(vec![
+        InlineString("\u{a9} \u{a9} ".to_string()),
+        Code(MdString::from_vec(vec![InlineString("&copy;".to_string())])),
+    ]);
+    assert_eq!(md_string, result);
+}
+
+#[test]
+fn test_mdline_sub_super() {
+    let mut md_string = MdString::new();
+    from_tokens_to_mdinline(
+        &mut inline_tokens::tokenize("H~2~O is 2^10^ a=b".to_string()).iter(),
+        &mut md_string,
+        None,
+        false,
+    );
+
+    let result = MdString::from_vec(vec![
+        InlineString("H".to_string()),
+        Sub(MdString::from_vec(vec![InlineString("2".to_string())])),
+        InlineString("O is 2".to_string()),
+        Super(MdString::from_vec(vec![InlineString("10".to_string())])),
+        InlineString(" a=b".to_string()),
+    ]);
+    assert_eq!(md_string, result);
+
+    // spaces and line breaks can't be inside, unless escaped
+    let md_string = MdString::from_string("snake ~ tilde and x^2\\ b^ ~a\nb~ x^^".to_string());
+    let result = MdString::from_vec(vec![
+        InlineString("snake ~ tilde and x".to_string()),
+        Super(MdString::from_vec(vec![InlineString("2\u{a0}b".to_string())])),
+        InlineString(" ~a".to_string()),
+        SoftBreak,
+        InlineString("b~ x^^".to_string()),
+    ]);
+    assert_eq!(md_string, result);
+}
+
+#[test]
+fn test_mdline_wiki_link() {
+    let mut md_string = MdString::new();
+    from_tokens_to_mdinline(
+        &mut inline_tokens::tokenize(
+            "[[My Page#Some Head | the page]] ![[a.png]] ![[b.png|200x100]] [[2|3]]".to_string(),
+        )
+        .iter(),
+        &mut md_string,
+        None,
+        false,
+    );
+
+    let result = MdString::from_vec(vec![
+        WikiLink {
+            target: "My Page".to_string(),
+            anchor: Some("Some Head".to_string()),
+            label: Some("the page".to_string()),
+            embed: false,
+            attrs: Attributes::default(),
+        },
+        InlineString(" ".to_string()),
+        WikiLink {
+            target: "a.png".to_string(),
+            anchor: None,
+            label: None,
+            embed: true,
+            attrs: Attributes::default(),
+        },
+        InlineString(" ".to_string()),
+        WikiLink {
+            target: "b.png".to_string(),
+            anchor: None,
+            label: None,
+            embed: true,
+            attrs: Attributes::from_braces("{width=200 height=100}").unwrap(),
+        },
+        InlineString(" ".to_string()),
+        // only embeds have a size
+        WikiLink {
+            target: "2".to_string(),
+            anchor: None,
+            label: Some("3".to_string()),
+            embed: false,
+            attrs: Attributes::default(),
+        },
+    ]);
+    assert_eq!(md_string, result);
+}
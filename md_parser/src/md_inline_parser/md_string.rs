@@ -1,7 +1,15 @@
-use super::md_inline::MdInline;
+use std::slice::{Iter, IterMut};
 
-#[derive(Debug, PartialEq)]
-pub(crate) struct MdString {
+use super::{
+    inline_tokens::tokenize_with,
+    md_inline::{from_tokens_to_mdinline, MdInline},
+    references::find_references,
+    smart_punctuation::smarten,
+};
+use crate::md_options::ParserOptions;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct MdString {
     string: Vec<MdInline>,
 }
 
@@ -18,15 +26,65 @@ impl MdString {
         }
     }
 
+    ///
+    /// parses the inline elements of `data`,
+    /// lines of a paragraph are expected to be joined with `\n`.
+    ///
     pub fn from_string(data: String) -> MdString {
-        unimplemented!()
+        MdString::from_string_with(data, &ParserOptions::default())
+    }
+
+    pub fn from_string_with(data: String, options: &ParserOptions) -> MdString {
+        let mut md_string = MdString::new();
+        from_tokens_to_mdinline(
+            &mut tokenize_with(data, options).iter(),
+            &mut md_string,
+            None,
+            false,
+        );
+        if options.mentions || options.issue_refs || options.hashtags {
+            find_references(&mut md_string, options);
+        }
+        if options.smart_punctuation {
+            smarten(&mut md_string);
+        }
+        md_string
     }
 
     pub fn push(&mut self, val: MdInline) {
         self.string.push(val);
     }
 
+    /// pushes text, merging it into the last `InlineString` if there is one
+    pub fn push_str(&mut self, val: &str) {
+        if let Some(MdInline::InlineString(s)) = self.string.last_mut() {
+            s.push_str(val);
+        } else {
+            self.string.push(MdInline::InlineString(val.to_owned()));
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<MdInline> {
+        self.string.pop()
+    }
+
     pub fn last_mut(&mut self) -> Option<&mut MdInline> {
         self.string.last_mut()
     }
+
+    pub fn iter(&self) -> Iter<'_, MdInline> {
+        self.string.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, MdInline> {
+        self.string.iter_mut()
+    }
+
+    pub fn into_vec(self) -> Vec<MdInline> {
+        self.string
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.string.is_empty()
+    }
 }
@@ -1,12 +1,29 @@
 use std::alloc::Allocator;
 
+pub(crate) mod emoji;
+pub(crate) mod entities;
 mod inline_tokens;
-mod md_inline;
+pub(crate) mod md_inline;
 ///
 /// After Blocks have been parsed we come here
 /// to find Inline Elements
 ///
 pub mod md_string;
+pub mod references;
+mod smart_punctuation;
+
+///
+/// byte length of the bare url `data` starts with, a word with `://`
+/// in it or starting with `www.`, which goes on till a space.
+///
+/// it's the same for emoji, references and smart punctuation,
+/// so they all leave the same part of the text alone.
+///
+pub(crate) fn url_len(data: &str) -> Option<usize> {
+    let len = data.find(char::is_whitespace).unwrap_or(data.len());
+    let word = &data[..len];
+    (word.contains("://") || word.starts_with("www.")).then_some(len)
+}
 
 pub(crate) trait VecLastMutIfMatch<T, P>
 where
@@ -22,7 +39,7 @@ where
 {
     fn last_mut_if(&mut self, f: P) -> Option<&mut T> {
         if let Some(x) = self.last_mut() {
-            if f(&x) {
+            if f(x) {
                 return Some(x);
             }
         }
@@ -0,0 +1,210 @@
+use std::fmt;
+
+use super::{md_inline::MdInline, md_string::MdString, url_len};
+use crate::md_options::ParserOptions;
+
+///
+/// `@user`, `#123`, `org/repo#45` or `#tag` found in text
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reference {
+    /// `@user`
+    Mention(String),
+    /// `#123`, or `org/repo#45` with the repo
+    Issue { repo: Option<String>, number: u64 },
+    /// `#tag`
+    Hashtag(String),
+}
+
+/// writes the reference back the way it's written in text
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reference::Mention(user) => write!(f, "@{}", user),
+            Reference::Issue { repo: None, number } => write!(f, "#{}", number),
+            Reference::Issue {
+                repo: Some(repo),
+                number,
+            } => write!(f, "{}#{}", repo, number),
+            Reference::Hashtag(tag) => write!(f, "#{}", tag),
+        }
+    }
+}
+
+///
+/// turns the references in text of `md_string` into `Reference`s,
+/// which kinds are picked up is up to `options`.
+///
+/// code, links and bare urls are left alone, and a reference has
+/// to start a word, so `me@mail.com` isn't a mention.
+///
+pub(crate) fn find_references(md_string: &mut MdString, options: &ParserOptions) {
+    for inline in md_string.iter_mut() {
+        match inline {
+            MdInline::Bold(s)
+            | MdInline::Italic(s)
+            | MdInline::BoldItalic(s)
+            | MdInline::Strike(s)
+            | MdInline::Highlight(s)
+            | MdInline::Sub(s)
+            | MdInline::Super(s) => find_references(s, options),
+            _ => (),
+        }
+    }
+    let mut out = MdString::new();
+    for inline in std::mem::take(md_string).into_vec() {
+        match inline {
+            MdInline::InlineString(text) => split_text(&text, options, &mut out),
+            inline => out.push(inline),
+        }
+    }
+    *md_string = out;
+}
+
+fn split_text(text: &str, options: &ParserOptions, out: &mut MdString) {
+    let mut plain = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        let prev = text[..pos].chars().next_back();
+        if let Some(len) = url_len(&text[pos..]).filter(|_| prev.is_none_or(char::is_whitespace)) {
+            pos += len;
+            continue;
+        }
+        let found = match prev {
+            Some(prev) if is_name_char(prev) || matches!(prev, '.' | '/' | '@' | '#') => None,
+            _ => reference_at(&text[pos..], options),
+        };
+        match found {
+            Some((len, reference)) => {
+                if plain < pos {
+                    out.push_str(&text[plain..pos]);
+                }
+                out.push(MdInline::Reference(reference));
+                pos += len;
+                plain = pos;
+            }
+            None => pos += text[pos..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if plain < text.len() {
+        out.push_str(&text[plain..]);
+    }
+}
+
+/// reference at the start of `data`, with its byte length
+fn reference_at(data: &str, options: &ParserOptions) -> Option<(usize, Reference)> {
+    if let Some(rest) = data.strip_prefix('@') {
+        let len = name_len(rest);
+        let user = rest[..len].trim_end_matches('-');
+        let valid = options.mentions
+            && user.starts_with(|ch: char| ch.is_ascii_alphanumeric())
+            && ends_word(&rest[user.len()..]);
+        return valid.then(|| (user.len() + 1, Reference::Mention(user.to_string())));
+    }
+    if let Some(rest) = data.strip_prefix('#') {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if digits > 0 {
+            let number = rest[..digits].parse().ok()?;
+            let valid = options.issue_refs && ends_word(&rest[digits..]);
+            return valid.then_some((digits + 1, Reference::Issue { repo: None, number }));
+        }
+        let len = name_len(rest);
+        let tag = rest[..len].trim_end_matches('-');
+        let valid = options.hashtags
+            && tag.starts_with(char::is_alphabetic)
+            && ends_word(&rest[tag.len()..]);
+        return valid.then(|| (tag.len() + 1, Reference::Hashtag(tag.to_string())));
+    }
+    if !options.issue_refs {
+        return None;
+    }
+    // org/repo#45
+    let owner = name_len(data);
+    let rest = data[owner..].strip_prefix('/')?;
+    let repo = rest
+        .find(|ch: char| !(is_name_char(ch) || ch == '.'))
+        .unwrap_or(rest.len());
+    let rest = rest[repo..].strip_prefix('#')?;
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if owner == 0 || repo == 0 || digits == 0 || !ends_word(&rest[digits..]) {
+        return None;
+    }
+    let len = owner + repo + digits + 2;
+    let reference = Reference::Issue {
+        repo: Some(data[..owner + repo + 1].to_string()),
+        number: rest[..digits].parse().ok()?,
+    };
+    Some((len, reference))
+}
+
+/// byte length of the name at the start of `data`
+fn name_len(data: &str) -> usize {
+    data.find(|ch| !is_name_char(ch)).unwrap_or(data.len())
+}
+
+fn is_name_char(ch: char) -> bool {
+    ch.is_alphanumeric() || matches!(ch, '_' | '-')
+}
+
+/// nothing right after a reference which would make it a longer word
+fn ends_word(rest: &str) -> bool {
+    let mut chars = rest.chars();
+    match chars.next() {
+        None => true,
+        // `@user.name` or `#1.5` go on, `@user.` doesn't
+        Some('.' | '@' | '/') => !chars.next().is_some_and(char::is_alphanumeric),
+        Some(ch) => !is_name_char(ch),
+    }
+}
+
+#[cfg(test)]
+mod references_testing {
+    use super::*;
+
+    fn found(data: &str) -> MdString {
+        let options = ParserOptions {
+            mentions: true,
+            issue_refs: true,
+            hashtags: true,
+            ..ParserOptions::default()
+        };
+        MdString::from_string_with(data.to_string(), &options)
+    }
+
+    #[test]
+    fn all_kinds() {
+        assert_eq!(
+            found("@ana, see #12 and rust-lang/rust#45 #tag."),
+            MdString::from_vec(vec![
+                MdInline::Reference(Reference::Mention("ana".to_string())),
+                MdInline::InlineString(", see ".to_string()),
+                MdInline::Reference(Reference::Issue {
+                    repo: None,
+                    number: 12
+                }),
+                MdInline::InlineString(" and ".to_string()),
+                MdInline::Reference(Reference::Issue {
+                    repo: Some("rust-lang/rust".to_string()),
+                    number: 45
+                }),
+                MdInline::InlineString(" ".to_string()),
+                MdInline::Reference(Reference::Hashtag("tag".to_string())),
+                MdInline::InlineString(".".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn left_alone() {
+        let data = "me@mail.com `@ana` [@ana](https://x.y/@ana) https://x.y/?a=@b #1a C#";
+        let md_string = found(data);
+        assert!(!md_string
+            .iter()
+            .any(|inline| matches!(inline, MdInline::Reference(_))));
+        // turned off by default
+        assert_eq!(
+            MdString::from_string("@ana #1 #tag".to_string()),
+            MdString::from_vec(vec![MdInline::InlineString("@ana #1 #tag".to_string())])
+        );
+    }
+}
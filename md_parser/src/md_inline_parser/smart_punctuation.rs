@@ -0,0 +1,123 @@
+use super::{md_inline::MdInline, md_string::MdString, url_len};
+
+///
+/// turns straight quotes into curly ones, `--` and `---` into
+/// en and em dashes, and `...` into an ellipsis.
+///
+/// only text is changed, code, urls and math are left alone.
+/// a quote is an opening one at the start or after a space or an
+/// opening bracket, otherwise it's a closing one or an apostrophe.
+///
+pub(crate) fn smarten(md_string: &mut MdString) {
+    Smartener { prev: None }.smarten(md_string);
+}
+
+struct Smartener {
+    /// last character before the current one, across inlines
+    prev: Option<char>,
+}
+
+impl Smartener {
+    fn smarten(&mut self, md_string: &mut MdString) {
+        for inline in md_string.iter_mut() {
+            match inline {
+                MdInline::Bold(s)
+                | MdInline::Italic(s)
+                | MdInline::BoldItalic(s)
+                | MdInline::Strike(s)
+                | MdInline::Highlight(s)
+                | MdInline::Sub(s)
+                | MdInline::Super(s)
+                | MdInline::LinkText(s)
+                | MdInline::Image { alt: s, .. } => self.smarten(s),
+                MdInline::InlineString(s) => *s = self.smarten_text(s),
+                MdInline::SoftBreak | MdInline::HardBreak => self.prev = Some(' '),
+                // a quote right after these closes
+                MdInline::Code(_)
+                | MdInline::WikiLink { .. }
+                | MdInline::Math(_)
+                | MdInline::DisplayMath(_)
+                | MdInline::Emoji { .. }
+                | MdInline::Abbr { .. }
+                | MdInline::Reference(_) => self.prev = Some('x'),
+                MdInline::LinkUrl(_)
+                | MdInline::Footnote(_)
+                | MdInline::Attributes(_)
+                | MdInline::Comment(_) => (),
+            }
+        }
+    }
+
+    fn smarten_text(&mut self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(ch) = rest.chars().next() {
+            let next = rest[ch.len_utf8()..].chars().next();
+            let url = url_len(rest).filter(|_| self.prev.is_none_or(char::is_whitespace));
+            if let Some(end) = url {
+                // bare urls are kept as they are
+                out.push_str(&rest[..end]);
+                self.prev = rest[..end].chars().last();
+                rest = &rest[end..];
+                continue;
+            }
+            let (smart, len) = if rest.starts_with("---") {
+                ('\u{2014}', 3)
+            } else if rest.starts_with("--") {
+                ('\u{2013}', 2)
+            } else if rest.starts_with("...") {
+                ('\u{2026}', 3)
+            } else if ch == '"' {
+                (if self.opens() { '\u{201c}' } else { '\u{201d}' }, 1)
+            } else if ch == '\'' {
+                // `'90s` starts with an apostrophe
+                let opens = self.opens() && !next.is_some_and(|next| next.is_ascii_digit());
+                (if opens { '\u{2018}' } else { '\u{2019}' }, 1)
+            } else {
+                (ch, ch.len_utf8())
+            };
+            out.push(smart);
+            self.prev = Some(smart);
+            rest = &rest[len..];
+        }
+        out
+    }
+
+    /// a quote here would be an opening one
+    fn opens(&self) -> bool {
+        match self.prev {
+            None => true,
+            Some(prev) => {
+                prev.is_whitespace()
+                    || matches!(prev, '(' | '[' | '{' | '\u{201c}' | '\u{2018}')
+                    || matches!(prev, '\u{2013}' | '\u{2014}')
+            }
+        }
+    }
+}
+
+#[test]
+fn test_smarten() {
+    let mut md_string = MdString::from_string(
+        "\"She said 'hi'\" -- it's the '90s... a---b \"**bold**\" `'code'` \
+         https://a.b/x--y"
+            .to_string(),
+    );
+    smarten(&mut md_string);
+    assert_eq!(
+        md_string,
+        MdString::from_vec(vec![
+            MdInline::InlineString(
+                "\u{201c}She said \u{2018}hi\u{2019}\u{201d} \u{2013} it\u{2019}s the \
+                 \u{2019}90s\u{2026} a\u{2014}b \u{201c}"
+                    .to_string()
+            ),
+            MdInline::Bold(MdString::from_vec(vec![MdInline::InlineString("bold".to_string())])),
+            MdInline::InlineString("\u{201d} ".to_string()),
+            MdInline::Code(MdString::from_vec(vec![MdInline::InlineString(
+                "'code'".to_string()
+            )])),
+            MdInline::InlineString(" https://a.b/x--y".to_string()),
+        ])
+    );
+}
@@ -1,4 +1,9 @@
-use crate::md_line_reader::{to_mdline, to_mdlines, MdRawLine};
+use crate::{
+    md_attributes::Attributes,
+    md_inline_parser::md_inline::split_destination,
+    md_line_reader::{list_marker_len, to_mdline_with, MdRawLine},
+    md_options::ParserOptions,
+};
 
 ///
 /// Here lies implimentations for MdLine
@@ -14,14 +19,14 @@ pub(crate) enum PurifiedMdLine {
     Head {
         title: String,
         level: u8,
-        id: String,
+        attrs: Attributes,
     },
     Quote {
         nest_level: u8,
         inside_md: Box<PurifiedMdLine>,
     },
     OList {
-        list_number: u8,
+        list_number: u32,
         list_text: String,
     },
     UList {
@@ -30,16 +35,41 @@ pub(crate) enum PurifiedMdLine {
     Image {
         alt_text: String,
         link_text: String,
+        title: Option<String>,
+        attrs: Attributes,
     },
     Table {
         row: Vec<String>,
     },
+    /// text after `| `, leading spaces are kept
+    LineBlock(String),
     Definition {
         def_text: String,
     },
+    /// `::: name attrs`
+    ContainerStart {
+        name: String,
+        attrs: Attributes,
+    },
+    /// `:::` alone
+    ContainerEnd,
+    /// `*[abbr]: title`
+    Abbreviation {
+        abbr: String,
+        title: String,
+    },
+    /// first line of a comment block
+    Comment(String),
+    /// `{{#include path attrs}}`
+    Include {
+        path: String,
+        attrs: Attributes,
+    },
     TaskedLine {
         task_text: String,
         done: bool,
+        /// `None` when it's in a bullet list
+        list_number: Option<u32>,
     },
     TabbedLine {
         level: u8,
@@ -49,22 +79,38 @@ pub(crate) enum PurifiedMdLine {
     EmptyLine,
     HR,
     Text(String),
-    CodeBlock,
+    CodeBlock {
+        lang: Option<String>,
+        attrs: Attributes,
+    },
+    MathBlock,
+    CodeLine(String),
 }
 
 impl PurifiedMdLine {
     pub fn purify(md_line: MdRawLine) -> PurifiedMdLine {
+        PurifiedMdLine::purify_with(md_line, &ParserOptions::default())
+    }
+
+    pub fn purify_with(md_line: MdRawLine, options: &ParserOptions) -> PurifiedMdLine {
         match md_line {
             MdRawLine::Head(s) => PurifiedMdLine::purify_head(s),
-            MdRawLine::Quote(s) => PurifiedMdLine::purify_quote(s),
+            MdRawLine::Quote(s) => PurifiedMdLine::purify_quote(s, options),
             MdRawLine::OList(s) => PurifiedMdLine::purify_olist(s),
             MdRawLine::UList(s) => PurifiedMdLine::purify_ulist(s),
             MdRawLine::Image(s) => PurifiedMdLine::purify_image(s),
             MdRawLine::Table(s) => PurifiedMdLine::purify_table(s),
-            MdRawLine::CodeBlock => PurifiedMdLine::CodeBlock,
+            MdRawLine::LineBlock(s) => PurifiedMdLine::purify_line_block(s),
+            MdRawLine::CodeBlock(s) => PurifiedMdLine::purify_fence(s),
+            MdRawLine::MathBlock => PurifiedMdLine::MathBlock,
+            MdRawLine::CodeLine(s) => PurifiedMdLine::CodeLine(s),
             MdRawLine::Definition(s) => PurifiedMdLine::purify_definition(s),
+            MdRawLine::Container(s) => PurifiedMdLine::purify_container(s),
+            MdRawLine::Abbreviation(s) => PurifiedMdLine::purify_abbreviation(s),
+            MdRawLine::Include(s) => PurifiedMdLine::purify_include(s),
+            MdRawLine::Comment(s) => PurifiedMdLine::Comment(s),
             MdRawLine::TaskLine(s) => PurifiedMdLine::purify_taskline(s),
-            MdRawLine::TabbedLine(s) => PurifiedMdLine::purify_tabbedline(s),
+            MdRawLine::TabbedLine(s) => PurifiedMdLine::purify_tabbedline(s, options),
             MdRawLine::HR => PurifiedMdLine::HR,
             MdRawLine::Text(s) => PurifiedMdLine::Text(s),
             MdRawLine::EmptyLine => PurifiedMdLine::EmptyLine,
@@ -77,7 +123,7 @@ impl PurifiedMdLine {
         for &ch in data.as_bytes().iter() {
             // count `#` until Space
             if ch == b'#' {
-                hash_count = hash_count + 1;
+                hash_count += 1;
             } else if ch == b' ' {
                 break;
             } else {
@@ -85,38 +131,32 @@ impl PurifiedMdLine {
                 return PurifiedMdLine::FailedText(data);
             }
         }
-        if hash_count > 6 && hash_count < 1 {
+        if !(1..=6).contains(&hash_count) {
             // if out of bounds break;
             return PurifiedMdLine::FailedText(data);
         }
 
         // #head_2 should have space between hashes and head_text
-        let mut head_text = String::new();
-        let mut custom_id_part = String::new(); // can find here.
-        for text in data.split(' ').skip(1) {
-            if text.starts_with("{#") && text.ends_with("}") {
-                custom_id_part.push_str(text);
-                break;
-            } else {
-                head_text.push_str(text);
-                head_text.push(' ');
+        let mut head_text = data[hash_count as usize..].trim();
+
+        // OPTIONAL: after head_text may have space and then attributes
+        // in curly braces, `{#custom-id .class key=value}`
+        let mut attrs = Attributes::default();
+        if let Some(start) = head_text.rfind(" {") {
+            if let Some(parsed) = Attributes::from_braces(&head_text[start + 1..]) {
+                attrs = parsed;
+                head_text = head_text[..start].trim_end();
             }
         }
 
-        // OPTIONAL: after head_text may have space and then a custom_id in curly braces
-        let mut custom_id = String::new();
-        if !custom_id_part.is_empty() {
-            custom_id.push_str(&custom_id_part.get(2..(custom_id_part.len() - 1)).unwrap())
-        }
-
         PurifiedMdLine::Head {
-            title: head_text.trim_end().to_string(),
+            title: head_text.to_string(),
             level: hash_count,
-            id: custom_id,
+            attrs,
         }
     }
 
-    pub fn purify_quote(mut quotes: String) -> PurifiedMdLine {
+    pub fn purify_quote(mut quotes: String, options: &ParserOptions) -> PurifiedMdLine {
         // count ">" and that is the level
         // everything after is text
         // series of ">" & after_text is divided by the "Space"
@@ -124,25 +164,25 @@ impl PurifiedMdLine {
             let data = quotes.split_off(space_position);
             PurifiedMdLine::Quote {
                 nest_level: quotes.len() as u8,
-                inside_md: Box::new(PurifiedMdLine::purify(to_mdline(
-                    data.get(1..).unwrap().to_string(),
-                ))),
+                inside_md: Box::new(PurifiedMdLine::purify_with(
+                    to_mdline_with(data.get(1..).unwrap().to_string(), options),
+                    options,
+                )),
             }
         } else {
             PurifiedMdLine::FailedText(quotes)
         }
     }
 
-    pub fn purify_olist(mut data: String) -> PurifiedMdLine {
-        // find first ". ", which of length 2
-        if let Some(dotspace_position) = data.find(". ") {
-            let text = data.split_off(dotspace_position);
-            PurifiedMdLine::OList {
-                list_number: data.parse::<u8>().unwrap(),
-                list_text: text.get(2..).unwrap().to_string(),
-            }
-        } else {
-            PurifiedMdLine::FailedText(data)
+    pub fn purify_olist(data: String) -> PurifiedMdLine {
+        // number followed by ". " or ") "
+        let digits = data.bytes().take_while(|b| b.is_ascii_digit()).count();
+        match (data.get(digits..digits + 2), data[..digits].parse::<u32>()) {
+            (Some(". " | ") "), Ok(list_number)) => PurifiedMdLine::OList {
+                list_number,
+                list_text: data[digits + 2..].to_string(),
+            },
+            _ => PurifiedMdLine::FailedText(data),
         }
     }
 
@@ -158,40 +198,68 @@ impl PurifiedMdLine {
     }
 
     pub fn purify_image(data: String) -> PurifiedMdLine {
-        let mut image_text = data.trim().to_owned();
-        // ![alt_text](link_text)
-        if let Some(seperate_pos) = image_text.find("](") {
-            let link_text = image_text.split_off(seperate_pos);
-            PurifiedMdLine::Image {
-                alt_text: image_text.get(2..).unwrap().trim().to_string(),
-                link_text: link_text
-                    .get(2..(link_text.len() - 1))
-                    .unwrap()
-                    .trim()
-                    .to_string(),
+        // ![alt_text](link_text "title" =WxH){attrs}, and nothing else on the line
+        let mut image_text = data.trim();
+        let mut attrs = Attributes::default();
+        if let Some(start) = image_text.rfind("){") {
+            if let Some(parsed) = Attributes::from_braces(&image_text[start + 1..]) {
+                attrs = parsed;
+                image_text = &image_text[..start + 1];
             }
-        } else {
-            PurifiedMdLine::FailedText(data)
+        }
+        let parsed = image_text
+            .strip_prefix("![")
+            .and_then(|inner| inner.strip_suffix(')'))
+            .and_then(|inner| inner.split_once("]("))
+            .filter(|(alt_text, _)| !alt_text.contains("]("))
+            .and_then(|(alt_text, dest)| Some((alt_text, split_destination(dest)?)));
+        let Some((alt_text, (link_text, title, size))) = parsed else {
+            return PurifiedMdLine::FailedText(data);
+        };
+        // `=WxH` goes before the attributes in braces
+        attrs.pairs.splice(0..0, size.pairs);
+        PurifiedMdLine::Image {
+            alt_text: alt_text.trim().to_string(),
+            link_text,
+            title,
+            attrs,
         }
     }
 
     pub fn purify_table(data: String) -> PurifiedMdLine {
         let table_data = data.trim().to_owned();
-        if !(table_data.starts_with('|') && table_data.ends_with('|')) {
+        if !(table_data.starts_with('|')
+            && table_data.ends_with('|')
+            && !table_data.ends_with("\\|"))
+        {
             // user can put spaces at end, if spaces then trim and check
             return PurifiedMdLine::FailedText(data);
         }
 
         let mut table_elems = Vec::with_capacity(5);
-        // split and collect all strings
-        for elems in table_data.split('|') {
-            if !elems.is_empty() {
-                table_elems.push(elems.to_owned());
+        // split and collect all strings, `\|` stays inside the cell
+        let mut elems = String::new();
+        let mut escaped = false;
+        for ch in table_data.chars() {
+            if ch == '|' && !escaped {
+                if !elems.is_empty() {
+                    table_elems.push(std::mem::take(&mut elems));
+                }
+            } else {
+                elems.push(ch);
             }
+            escaped = ch == '\\' && !escaped;
         }
         PurifiedMdLine::Table { row: table_elems }
     }
 
+    pub fn purify_line_block(data: String) -> PurifiedMdLine {
+        // `|` and one space after it
+        let line = data.trim_end();
+        let line = line.strip_prefix('|').unwrap_or(line);
+        PurifiedMdLine::LineBlock(line.strip_prefix(' ').unwrap_or(line).to_string())
+    }
+
     pub fn purify_definition(data: String) -> PurifiedMdLine {
         // data is something that starts with ": "
         // take everything after ": "
@@ -200,33 +268,120 @@ impl PurifiedMdLine {
         }
     }
 
-    pub fn purify_taskline(mut data: String) -> PurifiedMdLine {
-        // text will come as either - [ ] or - [X]
-        if data.starts_with("- [ ] ") {
-            PurifiedMdLine::TaskedLine {
-                done: false,
-                task_text: data.split_off(6).to_owned(),
-            }
-        } else if data.starts_with("- [X] ") {
-            PurifiedMdLine::TaskedLine {
-                done: true,
-                task_text: data.split_off(6).to_owned(),
-            }
+    ///
+    /// info string of an opening fence, a language maybe followed
+    /// by attributes, `rust {.numbers}`. attributes can also be alone
+    /// in braces, `{.rust #main}`, where first class is the language.
+    ///
+    pub fn purify_fence(info: String) -> PurifiedMdLine {
+        let info = info.trim();
+        let (lang, rest) = if info.starts_with('{') {
+            ("", info)
         } else {
+            info.split_once(char::is_whitespace).unwrap_or((info, ""))
+        };
+        let rest = rest.trim();
+        let rest = rest
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .unwrap_or(rest);
+        let mut attrs = Attributes::parse(rest);
+        let lang = if !lang.is_empty() {
+            Some(lang.to_string())
+        } else if !attrs.classes.is_empty() {
+            Some(attrs.classes.remove(0))
+        } else {
+            None
+        };
+        PurifiedMdLine::CodeBlock { lang, attrs }
+    }
+
+    /// path of the file, then `lines=3-10 section="Title" shift=1`
+    pub fn purify_include(data: String) -> PurifiedMdLine {
+        let (path, rest) = data.split_once(char::is_whitespace).unwrap_or((&data, ""));
+        PurifiedMdLine::Include {
+            path: path.to_string(),
+            attrs: Attributes::parse(rest),
+        }
+    }
+
+    pub fn purify_container(data: String) -> PurifiedMdLine {
+        // any number of `:`, then name and attributes, which
+        // can also be in braces, `::: name {.class}` or `::: {.class}`
+        let rest = data.trim_start_matches(':').trim();
+        if rest.is_empty() {
+            return PurifiedMdLine::ContainerEnd;
+        }
+        let (name, attrs) = if rest.starts_with('{') {
+            ("", rest)
+        } else {
+            rest.split_once(char::is_whitespace).unwrap_or((rest, ""))
+        };
+        let attrs = attrs.trim();
+        let attrs = attrs
+            .strip_prefix('{')
+            .and_then(|attrs| attrs.strip_suffix('}'))
+            .unwrap_or(attrs);
+        PurifiedMdLine::ContainerStart {
+            name: name.to_string(),
+            attrs: Attributes::parse(attrs),
+        }
+    }
+
+    pub fn purify_abbreviation(data: String) -> PurifiedMdLine {
+        // `*[` abbr `]:` title, neither of them can be empty
+        let Some((abbr, title)) = data[2..].split_once("]:") else {
+            return PurifiedMdLine::FailedText(data);
+        };
+        let (abbr, title) = (abbr.trim(), title.trim());
+        if abbr.is_empty() || title.is_empty() || abbr.contains(['[', ']']) {
+            return PurifiedMdLine::FailedText(data);
+        }
+        PurifiedMdLine::Abbreviation {
+            abbr: abbr.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    pub fn purify_taskline(data: String) -> PurifiedMdLine {
+        // text will come as any list marker and then [ ], [x] or [X]
+        let Some(marker_len) = list_marker_len(&data) else {
             // reached unreachable!
-            PurifiedMdLine::FailedText(data)
+            return PurifiedMdLine::FailedText(data);
+        };
+        // ordered when the marker ends with `.` or `)`, too long numbers are cut to the max
+        let list_number = match data.as_bytes()[marker_len - 2] {
+            b'.' | b')' => Some(data[..marker_len - 2].parse::<u32>().unwrap_or(u32::MAX)),
+            _ => None,
+        };
+        let done = match data.get(marker_len..marker_len + 4) {
+            Some("[ ] ") => false,
+            Some("[x] " | "[X] ") => true,
+            _ => return PurifiedMdLine::FailedText(data),
+        };
+        PurifiedMdLine::TaskedLine {
+            done,
+            task_text: data[marker_len + 4..].to_string(),
+            list_number,
         }
     }
 
-    pub fn purify_tabbedline(mut data: String) -> PurifiedMdLine {
-        // 1. count tabs
+    pub fn purify_tabbedline(mut data: String, options: &ParserOptions) -> PurifiedMdLine {
+        // 1. count tabs, two spaces are taken as one tab
+        let mut tabs = 0_usize;
+        let mut spaces = 0_usize;
         for (i, ch) in data.char_indices() {
-            if ch != '\t' {
+            if ch == '\t' {
+                tabs += 1;
+            } else if ch == ' ' {
+                spaces += 1;
+            } else {
                 return PurifiedMdLine::TabbedLine {
-                    level: i as u8,
-                    text: Box::new(PurifiedMdLine::purify(to_mdline(
-                        data.split_off(i).to_owned(),
-                    ))),
+                    level: (tabs + spaces / 2).min(u8::MAX as usize) as u8,
+                    text: Box::new(PurifiedMdLine::purify_with(
+                        to_mdline_with(data.split_off(i).to_owned(), options),
+                        options,
+                    )),
                 };
             }
         }
@@ -246,7 +401,7 @@ mod purifier_testing {
             PurifiedMdLine::Head {
                 title: String::from("head 2"),
                 level: 2,
-                id: String::from("head-2")
+                attrs: Attributes::from_braces("{#head-2}").unwrap()
             }
         );
 
@@ -262,7 +417,7 @@ mod purifier_testing {
             PurifiedMdLine::Head {
                 title: String::from("head 2{#head-2}"),
                 level: 2,
-                id: String::new()
+                attrs: Attributes::default()
             }
         );
 
@@ -272,7 +427,33 @@ mod purifier_testing {
             PurifiedMdLine::Head {
                 title: String::from("head 2 {# head-2}"),
                 level: 2,
-                id: String::new()
+                attrs: Attributes::default()
+            }
+        );
+    }
+
+    #[test]
+    fn fence_purifier_test() {
+        let fence = |info: &str| PurifiedMdLine::purify(MdRawLine::CodeBlock(info.to_string()));
+        assert_eq!(
+            fence("rust {#main .numbers}"),
+            PurifiedMdLine::CodeBlock {
+                lang: Some("rust".to_string()),
+                attrs: Attributes::from_braces("{#main .numbers}").unwrap()
+            }
+        );
+        assert_eq!(
+            fence("{.python .x}"),
+            PurifiedMdLine::CodeBlock {
+                lang: Some("python".to_string()),
+                attrs: Attributes::from_braces("{.x}").unwrap()
+            }
+        );
+        assert_eq!(
+            fence(""),
+            PurifiedMdLine::CodeBlock {
+                lang: None,
+                attrs: Attributes::default()
             }
         );
     }
@@ -315,7 +496,7 @@ mod purifier_testing {
                 inside_md: Box::new(PurifiedMdLine::Head {
                     level: 1,
                     title: String::from("blockquote lask"),
-                    id: String::new()
+                    attrs: Attributes::default()
                 })
             }
         );
@@ -370,7 +551,9 @@ mod purifier_testing {
             PurifiedMdLine::purify(MdRawLine::Image(String::from("![alt text](image.jpg)"))),
             PurifiedMdLine::Image {
                 alt_text: "alt text".to_string(),
-                link_text: "image.jpg".to_string()
+                link_text: "image.jpg".to_string(),
+                title: None,
+                attrs: Attributes::default()
             }
         );
 
@@ -379,10 +562,40 @@ mod purifier_testing {
             PurifiedMdLine::purify(MdRawLine::Image(String::from("![alt text](image.jpg) "))),
             PurifiedMdLine::Image {
                 alt_text: "alt text".to_string(),
-                link_text: "image.jpg".to_string()
+                link_text: "image.jpg".to_string(),
+                title: None,
+                attrs: Attributes::default()
+            }
+        );
+
+        // attributes after the link
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::Image(String::from("![a](b.png){.wide width=50%}"))),
+            PurifiedMdLine::Image {
+                alt_text: "a".to_string(),
+                link_text: "b.png".to_string(),
+                title: None,
+                attrs: Attributes::from_braces("{.wide width=50%}").unwrap()
             }
         );
 
+        // title and size in the parens
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::Image(String::from("![a](b.png 'Big one' =640x)"))),
+            PurifiedMdLine::Image {
+                alt_text: "a".to_string(),
+                link_text: "b.png".to_string(),
+                title: Some("Big one".to_string()),
+                attrs: Attributes::from_braces("{width=640}").unwrap()
+            }
+        );
+
+        // an image with text after it is a paragraph
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::Image(String::from("![a](b.png) and text"))),
+            PurifiedMdLine::FailedText("![a](b.png) and text".to_string())
+        );
+
         // space in between braces and exclamation should fail
         // this will be filtered out in `MdLineReader`
         // assert_eq!(
@@ -419,6 +632,18 @@ mod purifier_testing {
             PurifiedMdLine::purify(MdRawLine::Table("| hello | world  ".to_string())),
             PurifiedMdLine::FailedText("| hello | world  ".to_string())
         );
+
+        // escaped pipes don't split the cells
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::Table("| a \\| b | c |".to_string())),
+            PurifiedMdLine::Table {
+                row: vec![" a \\| b ".to_string(), " c ".to_string()]
+            }
+        );
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::Table("| a | b \\|".to_string())),
+            PurifiedMdLine::FailedText("| a | b \\|".to_string())
+        );
     }
 
     #[test]
@@ -432,6 +657,23 @@ mod purifier_testing {
         );
     }
 
+    #[test]
+    fn abbreviation_purifier_test() {
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::Abbreviation(
+                "*[HTML]:  Hyper Text Markup Language ".to_string()
+            )),
+            PurifiedMdLine::Abbreviation {
+                abbr: "HTML".to_string(),
+                title: "Hyper Text Markup Language".to_string(),
+            }
+        );
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::Abbreviation("*[]: nothing".to_string())),
+            PurifiedMdLine::FailedText("*[]: nothing".to_string())
+        );
+    }
+
     #[test]
     fn tasked_purifier_test() {
         // this will only get lines starting with "- [ ] " or "- [X] "
@@ -439,14 +681,42 @@ mod purifier_testing {
             PurifiedMdLine::purify(MdRawLine::TaskLine("- [ ] todo 1".to_string())),
             PurifiedMdLine::TaskedLine {
                 task_text: "todo 1".to_string(),
-                done: false
+                done: false,
+                list_number: None
             }
         );
         assert_eq!(
             PurifiedMdLine::purify(MdRawLine::TaskLine("- [X] todo 1".to_string())),
             PurifiedMdLine::TaskedLine {
                 task_text: "todo 1".to_string(),
-                done: true
+                done: true,
+                list_number: None
+            }
+        );
+
+        // any list marker and lowercase x
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::TaskLine("* [x] todo 2".to_string())),
+            PurifiedMdLine::TaskedLine {
+                task_text: "todo 2".to_string(),
+                done: true,
+                list_number: None
+            }
+        );
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::TaskLine("3) [ ] todo 3".to_string())),
+            PurifiedMdLine::TaskedLine {
+                task_text: "todo 3".to_string(),
+                done: false,
+                list_number: Some(3)
+            }
+        );
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::TaskLine("300. [ ] todo 4".to_string())),
+            PurifiedMdLine::TaskedLine {
+                task_text: "todo 4".to_string(),
+                done: false,
+                list_number: Some(300)
             }
         );
     }
@@ -461,6 +731,17 @@ mod purifier_testing {
             }
         );
 
+        // two spaces make a level
+        assert_eq!(
+            PurifiedMdLine::purify(MdRawLine::TabbedLine("    - dfj".to_string())),
+            PurifiedMdLine::TabbedLine {
+                level: 2,
+                text: Box::new(PurifiedMdLine::UList {
+                    list_text: "dfj".to_string()
+                })
+            }
+        );
+
         // send the for further evaluation
         assert_eq!(
             PurifiedMdLine::purify(MdRawLine::TabbedLine("\t\t1. dfj".to_string())),
@@ -1,3 +1,5 @@
+use crate::md_options::ParserOptions;
+
 #[derive(Debug, Clone)]
 pub(crate) enum MdRawLine {
     /// Any line which starts with # will be transfered here.
@@ -6,65 +8,238 @@ pub(crate) enum MdRawLine {
     Quote(String),
     /// Line with `n. `
     OList(String),
-    /// Line with `- ` TODO: add for "* " "+ "
+    /// Line with `- `, `* ` or `+ `
     UList(String),
     /// Line starting with !
     Image(String),
     /// Table lines start with |
     Table(String),
-    /// make code skip until finds another `CodeEnd`
-    CodeBlock,
+    /// `| line` without a `|` at the end, or `|` alone
+    LineBlock(String),
+    /// make code skip until finds another `CodeEnd`,
+    /// has the info string after the opening fence.
+    CodeBlock(String),
+    /// `$$` alone on a line, fences display math like `CodeBlock`
+    MathBlock,
+    /// raw line found in between two `CodeBlock` or `MathBlock` fences
+    CodeLine(String),
     /// starts with `: `
     Definition(String),
-    /// starts with `- [X] ` or `- [ ]`
+    /// starts with `:::`, opens or closes a container
+    Container(String),
+    /// any list line with `[ ] `, `[x] ` or `[X] ` after the marker
     TaskLine(String),
-    /// start with `\t`
+    /// `*[ABBR]: expansion`
+    Abbreviation(String),
+    /// `{{#include path.md}}`, has what's after `#include`
+    Include(String),
+    /// `<!--` or `%%` comment starting the line and taking it all,
+    /// lines until it closes are `CodeLine`s.
+    Comment(String),
+    /// start with `\t`, or spaces before a list marker
     TabbedLine(String),
     HR,
     Text(String),
-    /// start with '\n'
+    /// line with nothing but whitespace
     EmptyLine,
 }
 
 pub fn to_mdline(line: String) -> MdRawLine {
-    if line.starts_with("#") {
-        MdRawLine::Head(line.clone())
+    to_mdline_with(line, &ParserOptions::default())
+}
+
+pub fn to_mdline_with(line: String, options: &ParserOptions) -> MdRawLine {
+    if line.trim().is_empty() {
+        MdRawLine::EmptyLine
+    } else if line.starts_with('\\') {
+        // escaped block marker `\# not head`, backslash
+        // itself is removed later by the inline tokenizer.
+        MdRawLine::Text(line)
+    } else if options.comments && block_comment(&line).is_some() {
+        MdRawLine::Comment(line)
+    } else if let Some(include) = include_directive(&line).filter(|_| options.includes) {
+        MdRawLine::Include(include.to_string())
+    } else if line.starts_with('#') {
+        MdRawLine::Head(line)
     } else if line.starts_with("> ") {
         MdRawLine::Quote(line)
-    } else if line.starts_with("- [ ] ") || line.starts_with("- [X] ") {
+    } else if is_thematic_break(&line) {
+        MdRawLine::HR
+    } else if options.abbreviations && line.starts_with("*[") && line.contains("]:") {
+        MdRawLine::Abbreviation(line)
+    } else if options.task_lists && is_task_line(&line) {
         MdRawLine::TaskLine(line)
     } else if starts_with_ordered_list_pattern(&line) {
         MdRawLine::OList(line)
-    } else if line.starts_with("- ") {
+    } else if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
         MdRawLine::UList(line)
-    } else if line.starts_with("---") {
-        MdRawLine::HR
     } else if line.starts_with("![") {
         MdRawLine::Image(line)
-    } else if line.starts_with("|") {
+    } else if options.line_blocks && is_line_block(&line) {
+        MdRawLine::LineBlock(line)
+    } else if line.starts_with('|') {
         MdRawLine::Table(line)
-    } else if line.starts_with("```") {
-        MdRawLine::CodeBlock
-    } else if line.starts_with(": ") {
+    } else if let Some(info) = line.strip_prefix("```") {
+        MdRawLine::CodeBlock(info.trim().to_string())
+    } else if options.math && line.trim_end() == "$$" {
+        MdRawLine::MathBlock
+    } else if options.containers && line.starts_with(":::") {
+        MdRawLine::Container(line)
+    } else if options.definitions && line.starts_with(": ") {
         MdRawLine::Definition(line)
-    } else if line.starts_with('\t') {
+    } else if line.starts_with('\t')
+        || (line.starts_with(' ') && list_marker_len(line.trim_start()).is_some())
+    {
         MdRawLine::TabbedLine(line)
-    } else if line.starts_with("\n") {
-        MdRawLine::EmptyLine
     } else {
-        MdRawLine::Text(line.clone())
+        MdRawLine::Text(line)
     }
 }
 
-pub fn to_mdlines(lines: Vec<String>) -> Vec<MdRawLine> {
-    lines
+pub fn to_mdlines(lines: Vec<String>, options: &ParserOptions) -> Vec<MdRawLine> {
+    numbered_mdlines(lines, options)
         .into_iter()
-        .map(|line| to_mdline(line))
-        .collect::<Vec<MdRawLine>>()
+        .map(|(_, _, md_line)| md_line)
+        .collect()
+}
+
+///
+/// `to_mdlines` along with the index of the line each comes from, and
+/// the byte in that line where it starts. a line which closes a comment
+/// and goes on after it gives two, the second starts after the comment.
+///
+pub(crate) fn numbered_mdlines(
+    lines: Vec<String>,
+    options: &ParserOptions,
+) -> Vec<(usize, usize, MdRawLine)> {
+    // lines in between fences are kept as they are
+    let mut in_code = false;
+    let mut in_math = false;
+    // end of the comment we're in
+    let mut in_comment: Option<&str> = None;
+    let mut md_lines = vec![];
+    for (i, mut line) in lines.into_iter().enumerate() {
+        let mut start = 0;
+        if in_code {
+            if line.starts_with("```") {
+                in_code = false;
+                md_lines.push((i, 0, MdRawLine::CodeBlock(String::new())));
+            } else {
+                md_lines.push((i, 0, MdRawLine::CodeLine(line)));
+            }
+            continue;
+        }
+        if in_math {
+            if line.trim_end() == "$$" {
+                in_math = false;
+                md_lines.push((i, 0, MdRawLine::MathBlock));
+            } else {
+                md_lines.push((i, 0, MdRawLine::CodeLine(line)));
+            }
+            continue;
+        }
+        if let Some(close) = in_comment {
+            let Some(end) = line.find(close) else {
+                md_lines.push((i, 0, MdRawLine::CodeLine(line)));
+                continue;
+            };
+            in_comment = None;
+            // text after the end is read as a line of its own
+            let rest = line.split_off(end + close.len());
+            md_lines.push((i, 0, MdRawLine::CodeLine(line)));
+            if rest.trim().is_empty() {
+                continue;
+            }
+            start = end + close.len() + (rest.len() - rest.trim_start().len());
+            line = rest.trim_start().to_string();
+        }
+        let md_line = to_mdline_with(line, options);
+        match &md_line {
+            MdRawLine::CodeBlock(_) => in_code = true,
+            MdRawLine::MathBlock => in_math = true,
+            MdRawLine::Comment(line) => in_comment = block_comment(line).flatten(),
+            _ => (),
+        }
+        md_lines.push((i, start, md_line));
+    }
+    md_lines
+}
+
+/// `| line` which isn't a table row, as it doesn't end with `|`
+fn is_line_block(line: &str) -> bool {
+    let line = line.trim_end();
+    line == "|" || (line.starts_with("| ") && !(line.ends_with('|') && !line.ends_with("\\|")))
+}
+
+///
+/// `Some` when all of `line` is a comment, or it starts one which goes
+/// on to the next lines, then it has the end to look for.
+///
+fn block_comment(line: &str) -> Option<Option<&'static str>> {
+    let line = line.trim();
+    let (open, close) = if line.starts_with("<!--") {
+        ("<!--", "-->")
+    } else if line.starts_with("%%") {
+        ("%%", "%%")
+    } else {
+        return None;
+    };
+    match line[open.len()..].find(close) {
+        None => Some(Some(close)),
+        Some(end) if open.len() + end + close.len() == line.len() => Some(None),
+        // text after it, so it's inside a paragraph
+        Some(_) => None,
+    }
+}
+
+/// what's after `#include` in `{{#include path.md lines=2-5}}`
+fn include_directive(line: &str) -> Option<&str> {
+    let inner = line.trim().strip_prefix("{{#include")?.strip_suffix("}}")?;
+    inner
+        .starts_with(char::is_whitespace)
+        .then(|| inner.trim())
+        .filter(|inner| !inner.is_empty())
+}
+
+///
+/// byte length of the list marker at the start of `line`,
+/// along with the space after it, `- `, `* `, `+ `, `1. ` or `1) `
+///
+pub(crate) fn list_marker_len(line: &str) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let marker = match bytes.first()? {
+        b'-' | b'*' | b'+' => 1,
+        _ => {
+            let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+            if digits == 0 || !matches!(bytes.get(digits), Some(b'.' | b')')) {
+                return None;
+            }
+            digits + 1
+        }
+    };
+    (bytes.get(marker) == Some(&b' ')).then_some(marker + 1)
+}
+
+///
+/// three or more `-`, `*` or `_` and nothing else but spaces,
+/// so `--- text` is just text.
+///
+fn is_thematic_break(line: &str) -> bool {
+    let mut marks = line.trim_end().chars().filter(|ch| *ch != ' ' && *ch != '\t');
+    let Some(mark) = marks.next().filter(|mark| matches!(mark, '-' | '*' | '_')) else {
+        return false;
+    };
+    !line.starts_with([' ', '\t']) && marks.clone().all(|ch| ch == mark) && marks.count() >= 2
+}
+
+fn is_task_line(line: &str) -> bool {
+    list_marker_len(line).is_some_and(|len| {
+        matches!(line.get(len..len + 4), Some("[ ] " | "[x] " | "[X] "))
+    })
 }
 
-fn starts_with_ordered_list_pattern(line: &String) -> bool {
-    let mut divs = line.split('.');
+fn starts_with_ordered_list_pattern(line: &str) -> bool {
+    let mut divs = line.split(['.', ')']);
     let before_dots = divs.next().unwrap();
     if before_dots.parse::<u32>().is_ok() {
         return true;
@@ -74,9 +249,69 @@ fn starts_with_ordered_list_pattern(line: &String) -> bool {
 
 #[test]
 fn test_ordered_list_check() {
-    assert!(starts_with_ordered_list_pattern(&String::from("1. jsdf")));
-    assert!(!starts_with_ordered_list_pattern(&String::from(" 1jsdf")));
-    assert!(!starts_with_ordered_list_pattern(&String::from(" 1jsdf.")));
-    assert!(starts_with_ordered_list_pattern(&String::from("1.jsdf.")));
-    assert!(starts_with_ordered_list_pattern(&String::from("1. ")));
+    assert!(starts_with_ordered_list_pattern("1. jsdf"));
+    assert!(!starts_with_ordered_list_pattern(" 1jsdf"));
+    assert!(!starts_with_ordered_list_pattern(" 1jsdf."));
+    assert!(starts_with_ordered_list_pattern("1.jsdf."));
+    assert!(starts_with_ordered_list_pattern("1. "));
+}
+
+#[test]
+fn test_task_lines() {
+    for line in ["- [ ] a", "* [x] a", "+ [X] a", "1. [ ] a", "12) [x] a"] {
+        assert!(matches!(to_mdline(line.to_string()), MdRawLine::TaskLine(_)));
+    }
+    assert!(matches!(to_mdline("-[ ] a".to_string()), MdRawLine::Text(_)));
+    assert!(matches!(to_mdline("- [y] a".to_string()), MdRawLine::UList(_)));
+    assert!(matches!(to_mdline("  * [ ] a".to_string()), MdRawLine::TabbedLine(_)));
+    assert!(matches!(to_mdline("  not a list".to_string()), MdRawLine::Text(_)));
+}
+
+#[test]
+fn test_escaped_block_markers() {
+    for line in ["\\# not head", "\\- not list", "\\> not quote", "\\| not | table |", "\\```"] {
+        assert!(matches!(to_mdline(line.to_string()), MdRawLine::Text(_)));
+    }
+    assert!(matches!(to_mdline("1\\. not list".to_string()), MdRawLine::Text(_)));
+}
+
+#[test]
+fn test_code_lines_kept_raw() {
+    let lines = to_mdlines(
+        vec![
+            "```".to_string(),
+            "# not a head".to_string(),
+            "".to_string(),
+            "```".to_string(),
+            "# head".to_string(),
+        ],
+        &ParserOptions::default(),
+    );
+    assert!(matches!(&lines[0], MdRawLine::CodeBlock(info) if info.is_empty()));
+    assert!(matches!(&lines[1], MdRawLine::CodeLine(s) if s == "# not a head"));
+    assert!(matches!(&lines[2], MdRawLine::CodeLine(s) if s.is_empty()));
+    assert!(matches!(lines[3], MdRawLine::CodeBlock(_)));
+    assert!(matches!(lines[4], MdRawLine::Head(_)));
+}
+
+#[test]
+fn test_comment_end_and_text() {
+    let lines = to_mdlines(
+        vec!["<!-- a".to_string(), "b --> # head".to_string(), "%% c %%".to_string()],
+        &ParserOptions::default(),
+    );
+    assert!(matches!(&lines[0], MdRawLine::Comment(s) if s == "<!-- a"));
+    assert!(matches!(&lines[1], MdRawLine::CodeLine(s) if s == "b -->"));
+    assert!(matches!(&lines[2], MdRawLine::Head(s) if s == "# head"));
+    assert!(matches!(lines[3], MdRawLine::Comment(_)));
+}
+
+#[test]
+fn test_thematic_breaks() {
+    for line in ["---", "-----", "***", "_ _ _", "- - -"] {
+        assert!(matches!(to_mdline(line.to_string()), MdRawLine::HR));
+    }
+    assert!(matches!(to_mdline("--- text".to_string()), MdRawLine::Text(_)));
+    assert!(matches!(to_mdline("--".to_string()), MdRawLine::Text(_)));
+    assert!(matches!(to_mdline("-*-".to_string()), MdRawLine::Text(_)));
 }
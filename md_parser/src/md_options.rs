@@ -0,0 +1,113 @@
+///
+/// turns on/off the extensions which are not part of CommonMark.
+///
+/// all of them but `smart_punctuation` and the chat references
+/// (`mentions`, `issue_refs`, `hashtags`) are on by default,
+/// `ParserOptions::commonmark()` turns all of them off.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParserOptions {
+    /// `==highlight==`
+    pub highlight: bool,
+    /// `^super^` and `~sub~`
+    pub sub_sup: bool,
+    /// `~~strike~~`
+    pub strikethrough: bool,
+    /// `[^note]` footnote references
+    pub footnotes: bool,
+    /// a term line followed by `: definition` lines
+    pub definitions: bool,
+    /// `- [ ] task` and `- [X] task`
+    pub task_lists: bool,
+    /// `$math$`, `$$math$$` and `$$` fenced blocks
+    pub math: bool,
+    /// `> [!NOTE]`, `> [!WARNING]` ... quotes as alerts
+    pub alerts: bool,
+    /// `::: name` ... `:::` containers
+    pub containers: bool,
+    /// `{#id .class key=value}` right after links and `[spans]`
+    pub attributes: bool,
+    /// `[[Page]]`, `[[Page#Heading|label]]` and `![[embed]]`
+    pub wiki_links: bool,
+    /// `:shortcode:` emoji, like `:rocket:`, a common subset of GitHub's names
+    pub emoji: bool,
+    /// `*[HTML]: Hyper Text Markup Language` abbreviations
+    pub abbreviations: bool,
+    /// `mermaid`, `dot` and `plantuml` code blocks as diagrams
+    pub diagrams: bool,
+    /// `{{#include path.md}}` lines, see `parser_md_with_includes`
+    pub includes: bool,
+    /// `<!-- comment -->` and `%% comment %%`, as blocks and inside text
+    pub comments: bool,
+    /// `| line` lines without a closing `|`, like pandoc's line blocks
+    pub line_blocks: bool,
+    /// `---` YAML or `+++` TOML front matter at the very start
+    pub front_matter: bool,
+    /// `@user` mentions
+    pub mentions: bool,
+    /// `#123` and `org/repo#45` issue references
+    pub issue_refs: bool,
+    /// `#tag` hashtags
+    pub hashtags: bool,
+    /// curly quotes, `--` and `---` dashes and `...` ellipses in text
+    pub smart_punctuation: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            highlight: true,
+            sub_sup: true,
+            strikethrough: true,
+            footnotes: true,
+            definitions: true,
+            task_lists: true,
+            math: true,
+            alerts: true,
+            containers: true,
+            attributes: true,
+            wiki_links: true,
+            emoji: true,
+            abbreviations: true,
+            diagrams: true,
+            includes: true,
+            comments: true,
+            line_blocks: true,
+            front_matter: true,
+            mentions: false,
+            issue_refs: false,
+            hashtags: false,
+            smart_punctuation: false,
+        }
+    }
+}
+
+impl ParserOptions {
+    /// strict CommonMark, without any extension
+    pub fn commonmark() -> Self {
+        ParserOptions {
+            highlight: false,
+            sub_sup: false,
+            strikethrough: false,
+            footnotes: false,
+            definitions: false,
+            task_lists: false,
+            math: false,
+            alerts: false,
+            containers: false,
+            attributes: false,
+            wiki_links: false,
+            emoji: false,
+            abbreviations: false,
+            diagrams: false,
+            includes: false,
+            comments: false,
+            line_blocks: false,
+            front_matter: false,
+            mentions: false,
+            issue_refs: false,
+            hashtags: false,
+            smart_punctuation: false,
+        }
+    }
+}
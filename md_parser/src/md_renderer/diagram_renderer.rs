@@ -0,0 +1,21 @@
+use crate::md_block::DiagramKind;
+
+///
+/// renders the source of a `Diagram` as HTML, at render time,
+/// say as SVG from a command line tool or from a cache.
+///
+/// `None` leaves the diagram to the default `<pre class="kind">`,
+/// which a script like mermaid.js can pick up.
+///
+pub trait DiagramRenderer {
+    fn render(&self, kind: DiagramKind, source: &str) -> Option<String>;
+}
+
+impl<F> DiagramRenderer for F
+where
+    F: Fn(DiagramKind, &str) -> Option<String>,
+{
+    fn render(&self, kind: DiagramKind, source: &str) -> Option<String> {
+        self(kind, source)
+    }
+}
@@ -0,0 +1,515 @@
+use std::ops::RangeInclusive;
+
+use crate::md_attributes::Attributes;
+
+///
+/// kind of a highlighted piece of code, which
+/// renderers turn into a class or a color.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Keyword,
+    Type,
+    /// `true`, `None`, `null` ...
+    Constant,
+    String,
+    Number,
+    Comment,
+    /// name right before `(`, or a rust macro
+    Function,
+    /// key of a JSON object, or of a TOML or YAML table
+    Property,
+    /// `$NAME` in shell
+    Variable,
+    Plain,
+}
+
+impl TokenKind {
+    /// class of the `<span>` around the token, `hl-keyword`, `hl-string` ...
+    pub fn class(&self) -> Option<&'static str> {
+        let class = match self {
+            TokenKind::Keyword => "hl-keyword",
+            TokenKind::Type => "hl-type",
+            TokenKind::Constant => "hl-constant",
+            TokenKind::String => "hl-string",
+            TokenKind::Number => "hl-number",
+            TokenKind::Comment => "hl-comment",
+            TokenKind::Function => "hl-function",
+            TokenKind::Property => "hl-property",
+            TokenKind::Variable => "hl-variable",
+            TokenKind::Plain => return None,
+        };
+        Some(class)
+    }
+}
+
+///
+/// how the code of a language looks, enough to color it,
+/// it's not a parser so it doesn't have to be exact.
+///
+struct Language {
+    names: &'static [&'static str],
+    keywords: &'static [&'static str],
+    types: &'static [&'static str],
+    constants: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// capitalized names are types
+    capitalized_types: bool,
+    /// keywords can be in any case, like in SQL
+    ignore_case: bool,
+    /// name or string before this is a key
+    key_separator: Option<char>,
+    /// `$NAME` and `${NAME}` are variables
+    dollar_variables: bool,
+}
+
+const NO_WORDS: &[&str] = &[];
+
+static LANGUAGES: [Language; 10] = [
+    Language {
+        names: &["rust", "rs"],
+        keywords: &[
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+            "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+            "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+            "type", "unsafe", "use", "where", "while",
+        ],
+        types: &[
+            "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "str",
+            "u8", "u16", "u32", "u64", "u128", "usize",
+        ],
+        constants: &["true", "false", "None", "Some", "Ok", "Err"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"'],
+        capitalized_types: true,
+        ignore_case: false,
+        key_separator: None,
+        dollar_variables: false,
+    },
+    Language {
+        names: &["python", "py"],
+        keywords: &[
+            "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+            "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in",
+            "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+            "with", "yield",
+        ],
+        types: &["bool", "bytes", "dict", "float", "int", "list", "object", "set", "str", "tuple"],
+        constants: &["True", "False", "None", "self"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        capitalized_types: true,
+        ignore_case: false,
+        key_separator: None,
+        dollar_variables: false,
+    },
+    Language {
+        names: &["javascript", "js", "jsx", "typescript", "ts", "tsx"],
+        keywords: &[
+            "as", "async", "await", "break", "case", "catch", "class", "const", "continue",
+            "default", "delete", "do", "else", "export", "extends", "finally", "for", "from",
+            "function", "if", "implements", "import", "in", "instanceof", "interface", "let",
+            "new", "of", "return", "static", "switch", "this", "throw", "try", "type", "typeof",
+            "var", "void", "while", "yield",
+        ],
+        types: &["any", "boolean", "never", "number", "string", "unknown"],
+        constants: &["true", "false", "null", "undefined", "NaN", "Infinity"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        capitalized_types: true,
+        ignore_case: false,
+        key_separator: None,
+        dollar_variables: false,
+    },
+    Language {
+        names: &["sh", "bash", "shell", "zsh", "console"],
+        keywords: &[
+            "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+            "in", "local", "return", "then", "until", "while",
+        ],
+        types: NO_WORDS,
+        constants: &["true", "false"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        capitalized_types: false,
+        ignore_case: false,
+        key_separator: None,
+        dollar_variables: true,
+    },
+    Language {
+        names: &["json", "jsonc"],
+        keywords: NO_WORDS,
+        types: NO_WORDS,
+        constants: &["true", "false", "null"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"'],
+        capitalized_types: false,
+        ignore_case: false,
+        key_separator: Some(':'),
+        dollar_variables: false,
+    },
+    Language {
+        names: &["toml"],
+        keywords: NO_WORDS,
+        types: NO_WORDS,
+        constants: &["true", "false"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        capitalized_types: false,
+        ignore_case: false,
+        key_separator: Some('='),
+        dollar_variables: false,
+    },
+    Language {
+        names: &["yaml", "yml"],
+        keywords: NO_WORDS,
+        types: NO_WORDS,
+        constants: &["true", "false", "null", "yes", "no", "on", "off"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        capitalized_types: false,
+        ignore_case: false,
+        key_separator: Some(':'),
+        dollar_variables: false,
+    },
+    Language {
+        names: &["sql"],
+        keywords: &[
+            "add", "all", "alter", "and", "as", "asc", "begin", "between", "by", "case", "commit",
+            "create", "delete", "desc", "distinct", "drop", "else", "end", "exists", "from",
+            "group", "having", "if", "in", "index", "inner", "insert", "into", "is", "join", "key",
+            "left", "like", "limit", "not", "offset", "on", "or", "order", "outer", "primary",
+            "references", "right", "select", "set", "table", "then", "union", "update", "values",
+            "view", "when", "where", "with",
+        ],
+        types: &[
+            "bigint", "blob", "boolean", "char", "date", "decimal", "float", "int", "integer",
+            "numeric", "real", "text", "timestamp", "varchar",
+        ],
+        constants: &["true", "false", "null"],
+        line_comments: &["--"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['\'', '"'],
+        capitalized_types: false,
+        ignore_case: true,
+        key_separator: None,
+        dollar_variables: false,
+    },
+    Language {
+        names: &["go", "golang"],
+        keywords: &[
+            "break", "case", "chan", "const", "continue", "default", "defer", "else",
+            "fallthrough", "for", "func", "go", "goto", "if", "import", "interface", "map",
+            "package", "range", "return", "select", "struct", "switch", "type", "var",
+        ],
+        types: &[
+            "bool", "byte", "error", "float32", "float64", "int", "int8", "int16", "int32",
+            "int64", "rune", "string", "uint", "uint8", "uint16", "uint32", "uint64", "uintptr",
+        ],
+        constants: &["true", "false", "nil", "iota"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        capitalized_types: true,
+        ignore_case: false,
+        key_separator: None,
+        dollar_variables: false,
+    },
+    Language {
+        names: &["c", "h"],
+        keywords: &[
+            "auto", "break", "case", "const", "continue", "default", "do", "else", "enum",
+            "extern", "for", "goto", "if", "inline", "register", "return", "sizeof", "static",
+            "struct", "switch", "typedef", "union", "volatile", "while", "#define", "#elif",
+            "#else", "#endif", "#if", "#ifdef", "#ifndef", "#include", "#pragma", "#undef",
+        ],
+        types: &[
+            "char", "double", "float", "int", "long", "short", "signed", "unsigned", "void",
+            "bool", "size_t", "int8_t", "int16_t", "int32_t", "int64_t", "uint8_t", "uint16_t",
+            "uint32_t", "uint64_t",
+        ],
+        constants: &["true", "false", "NULL"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+        capitalized_types: false,
+        ignore_case: false,
+        key_separator: None,
+        dollar_variables: false,
+    },
+];
+
+///
+/// splits `code` into highlighted tokens, which put together
+/// are `code` again. `None` if `lang` isn't known.
+///
+pub fn highlight<'a>(lang: &str, code: &'a str) -> Option<Vec<(TokenKind, &'a str)>> {
+    let lang = lang.to_ascii_lowercase();
+    let language = LANGUAGES.iter().find(|language| language.names.contains(&lang.as_str()))?;
+
+    let mut tokens: Vec<(TokenKind, &str)> = vec![];
+    let mut pos = 0;
+    while pos < code.len() {
+        let rest = &code[pos..];
+        let (kind, len) = next_token(language, code, pos);
+        let len = len.max(rest.chars().next().map_or(1, char::len_utf8));
+        // plain text next to plain text goes in one token
+        match tokens.last_mut() {
+            Some((TokenKind::Plain, text)) if kind == TokenKind::Plain => {
+                *text = &code[pos - text.len()..pos + len];
+            }
+            _ => tokens.push((kind, &rest[..len])),
+        }
+        pos += len;
+    }
+    Some(tokens)
+}
+
+/// kind and byte length of the token at `pos` in `code`
+fn next_token(language: &Language, code: &str, pos: usize) -> (TokenKind, usize) {
+    let rest = &code[pos..];
+    let prev = code[..pos].chars().next_back();
+    let ch = rest.chars().next().unwrap_or_default();
+
+    // `#` in shell only starts a comment at the start of a word
+    let word_start = prev.is_none_or(|prev| prev.is_whitespace() || prev == ';');
+    let comment = language
+        .line_comments
+        .iter()
+        .any(|start| rest.starts_with(start) && (*start != "#" || word_start));
+    if comment {
+        return (TokenKind::Comment, rest.find('\n').unwrap_or(rest.len()));
+    }
+    if let Some((open, close)) = language.block_comment {
+        if let Some(inner) = rest.strip_prefix(open) {
+            let len = inner.find(close).map_or(rest.len(), |end| open.len() + end + close.len());
+            return (TokenKind::Comment, len);
+        }
+    }
+
+    if language.quotes.contains(&ch) {
+        let len = string_len(rest, ch);
+        let is_key = language.key_separator.is_some_and(|sep| {
+            rest[len..].trim_start_matches([' ', '\t']).starts_with(sep)
+        });
+        return (if is_key { TokenKind::Property } else { TokenKind::String }, len);
+    }
+
+    if language.dollar_variables && ch == '$' {
+        let len = match rest[1..].chars().next() {
+            Some('{') => rest.find('}').map_or(rest.len(), |end| end + 1),
+            _ => 1 + word_len(&rest[1..]),
+        };
+        return (TokenKind::Variable, len);
+    }
+
+    let after_word = prev.is_some_and(|prev| prev.is_alphanumeric() || prev == '_');
+    if ch.is_ascii_digit() && !after_word {
+        let len = rest
+            .find(|ch: char| !(ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.')))
+            .unwrap_or(rest.len());
+        return (TokenKind::Number, len);
+    }
+
+    // `#include` is a word in C
+    let word = if ch == '#' && language.names[0] == "c" {
+        1 + word_len(&rest[1..])
+    } else {
+        word_len(rest)
+    };
+    if word == 0 || after_word {
+        return (TokenKind::Plain, ch.len_utf8());
+    }
+    let name = &rest[..word];
+    let after = rest[word..].trim_start_matches([' ', '\t']);
+    let is = |words: &[&str]| {
+        words.iter().any(|w| {
+            if language.ignore_case {
+                w.eq_ignore_ascii_case(name)
+            } else {
+                *w == name
+            }
+        })
+    };
+    let kind = if language.key_separator.is_some_and(|sep| after.starts_with(sep)) {
+        TokenKind::Property
+    } else if is(language.keywords) {
+        TokenKind::Keyword
+    } else if is(language.constants) {
+        TokenKind::Constant
+    } else if is(language.types)
+        || (language.capitalized_types && name.starts_with(|ch: char| ch.is_ascii_uppercase()))
+    {
+        TokenKind::Type
+    } else if after.starts_with('(') || (language.names[0] == "rust" && after.starts_with('!')) {
+        TokenKind::Function
+    } else {
+        TokenKind::Plain
+    };
+    (kind, word)
+}
+
+/// byte length of a name, `_` and letters then digits too
+fn word_len(data: &str) -> usize {
+    if !data.starts_with(|ch: char| ch.is_alphabetic() || ch == '_') {
+        return 0;
+    }
+    data.find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .unwrap_or(data.len())
+}
+
+///
+/// byte length of the string starting at `data` with `quote`, a
+/// backslash escapes the next character. only backticks and triple
+/// quotes go over line endings, the others end at the line.
+///
+fn string_len(data: &str, quote: char) -> usize {
+    let triple = quote.to_string().repeat(3);
+    if quote != '`' && data.starts_with(&triple) {
+        return data[3..].find(&triple).map_or(data.len(), |end| end + 6);
+    }
+    let mut chars = data.char_indices().skip(1);
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            '\n' if quote != '`' => return i,
+            ch if ch == quote => return i + 1,
+            _ => (),
+        }
+    }
+    data.len()
+}
+
+///
+/// lines to mark in a code block, from the info string like
+/// `rust {3-5}` or `rust {1,4-6}`, by their line number.
+///
+pub(crate) fn marked_lines(attrs: &Attributes) -> Vec<RangeInclusive<usize>> {
+    let mut ranges = vec![];
+    for part in attrs.args.iter().flat_map(|arg| arg.split(',')) {
+        let part = part.trim();
+        let range = match part.split_once('-') {
+            Some((start, end)) => start.parse().ok().zip(end.parse().ok()),
+            None => part.parse().ok().map(|line| (line, line)),
+        };
+        if let Some((start, end)) = range {
+            ranges.push(start..=end);
+        }
+    }
+    ranges
+}
+
+///
+/// number of the first line, when lines are numbered with
+/// `{.numbers}`, `start=3` starts them at 3.
+///
+pub(crate) fn first_line_number(attrs: &Attributes) -> Option<usize> {
+    attrs
+        .classes
+        .iter()
+        .any(|class| class == "numbers")
+        .then(|| attrs.get("start").and_then(|n| n.parse().ok()).unwrap_or(1))
+}
+
+///
+/// highlighted tokens split at line endings, so each line
+/// can be wrapped on its own. line endings are dropped.
+///
+pub(crate) fn token_lines<'a>(tokens: &[(TokenKind, &'a str)]) -> Vec<Vec<(TokenKind, &'a str)>> {
+    let mut lines = vec![vec![]];
+    for &(kind, text) in tokens {
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(vec![]);
+            }
+            if !part.is_empty() {
+                lines.last_mut().unwrap().push((kind, part));
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod highlight_testing {
+    use super::{highlight, marked_lines, TokenKind::*};
+    use crate::md_attributes::Attributes;
+
+    #[test]
+    fn rust_tokens() {
+        assert_eq!(
+            highlight("rust", "fn main() { let x: u8 = 1; println!(\"hi\"); } // done"),
+            Some(vec![
+                (Keyword, "fn"),
+                (Plain, " "),
+                (Function, "main"),
+                (Plain, "() { "),
+                (Keyword, "let"),
+                (Plain, " x: "),
+                (Type, "u8"),
+                (Plain, " = "),
+                (Number, "1"),
+                (Plain, "; "),
+                (Function, "println"),
+                (Plain, "!("),
+                (String, "\"hi\""),
+                (Plain, "); } "),
+                (Comment, "// done"),
+            ])
+        );
+        assert_eq!(highlight("brainfuck", "+++"), None);
+    }
+
+    #[test]
+    fn data_languages() {
+        assert_eq!(
+            highlight("json", "{\"a\": [true, 2]}"),
+            Some(vec![
+                (Plain, "{"),
+                (Property, "\"a\""),
+                (Plain, ": ["),
+                (Constant, "true"),
+                (Plain, ", "),
+                (Number, "2"),
+                (Plain, "]}"),
+            ])
+        );
+        assert_eq!(
+            highlight("sh", "echo \"$HOME\" $PATH # x#y"),
+            Some(vec![
+                (Plain, "echo "),
+                (String, "\"$HOME\""),
+                (Plain, " "),
+                (Variable, "$PATH"),
+                (Plain, " "),
+                (Comment, "# x#y"),
+            ])
+        );
+        assert_eq!(
+            highlight("SQL", "SELECT id FROM t"),
+            Some(vec![
+                (Keyword, "SELECT"),
+                (Plain, " id "),
+                (Keyword, "FROM"),
+                (Plain, " t"),
+            ])
+        );
+    }
+
+    #[test]
+    fn line_ranges() {
+        let attrs = Attributes::parse("1,4-6 8");
+        assert_eq!(marked_lines(&attrs), vec![1..=1, 4..=6, 8..=8]);
+    }
+}
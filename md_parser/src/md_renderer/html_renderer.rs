@@ -0,0 +1,939 @@
+use std::slice::Iter;
+
+use crate::{
+    md_attributes::Attributes,
+    md_block::{DefinitionItem, ListItem, MdBlock, TableRow},
+    md_inline_parser::{md_inline::MdInline, md_string::MdString, references::Reference},
+};
+
+use super::{
+    diagram_renderer::DiagramRenderer,
+    highlighter::{first_line_number, highlight, marked_lines, token_lines, TokenKind},
+    reference_resolver::ReferenceResolver,
+    wiki_resolver::{DefaultWikiResolver, WikiLinkResolver},
+};
+
+///
+/// how `:shortcode:` emoji are written
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmojiStyle {
+    /// just the emoji character
+    Unicode,
+    /// `<span class="emoji" title=":name:">` around the character
+    Span,
+    /// `<img class="emoji">` with `src` being the base url, the name and `.png`
+    Image(String),
+}
+
+///
+/// writes `MdBlock`s as HTML
+///
+pub struct HtmlRenderer {
+    wiki_resolver: Box<dyn WikiLinkResolver>,
+    emoji_style: EmojiStyle,
+    reference_resolver: Option<Box<dyn ReferenceResolver>>,
+    diagram_renderer: Option<Box<dyn DiagramRenderer>>,
+    highlighting: bool,
+    comments: bool,
+}
+
+impl Default for HtmlRenderer {
+    fn default() -> Self {
+        HtmlRenderer::new()
+    }
+}
+
+impl HtmlRenderer {
+    pub fn new() -> Self {
+        HtmlRenderer {
+            wiki_resolver: Box::new(DefaultWikiResolver),
+            emoji_style: EmojiStyle::Unicode,
+            reference_resolver: None,
+            diagram_renderer: None,
+            highlighting: true,
+            comments: false,
+        }
+    }
+
+    /// `resolver` gives the urls of `[[wiki links]]`
+    pub fn with_wiki_resolver(mut self, resolver: impl WikiLinkResolver + 'static) -> Self {
+        self.wiki_resolver = Box::new(resolver);
+        self
+    }
+
+    /// `resolver` gives the urls of `@user`, `#123` and `#tag`, without
+    /// one they are only marked with their class.
+    pub fn with_reference_resolver(mut self, resolver: impl ReferenceResolver + 'static) -> Self {
+        self.reference_resolver = Some(Box::new(resolver));
+        self
+    }
+
+    /// `renderer` gives the HTML of diagrams, in place of `<pre class="mermaid">`
+    pub fn with_diagram_renderer(mut self, renderer: impl DiagramRenderer + 'static) -> Self {
+        self.diagram_renderer = Some(Box::new(renderer));
+        self
+    }
+
+    /// code of known languages is split into `<span class="hl-keyword">` and
+    /// such, on by default.
+    pub fn with_highlighting(mut self, highlighting: bool) -> Self {
+        self.highlighting = highlighting;
+        self
+    }
+
+    /// comments are written as `<!-- -->`, they are left out by default
+    pub fn with_comments(mut self, comments: bool) -> Self {
+        self.comments = comments;
+        self
+    }
+
+    pub fn with_emoji_style(mut self, style: EmojiStyle) -> Self {
+        self.emoji_style = style;
+        self
+    }
+
+    pub fn render(&self, blocks: &[MdBlock]) -> String {
+        let mut out = String::new();
+        for block in blocks {
+            self.render_block(block, &mut out);
+        }
+        out
+    }
+
+    fn render_block(&self, block: &MdBlock, out: &mut String) {
+        match block {
+            MdBlock::Head { level, attrs, data } => {
+                out.push_str(&format!("<h{}{}>", level, render_attributes(attrs, None)));
+                self.render_inline(data, out);
+                out.push_str(&format!("</h{}>\n", level));
+            }
+            MdBlock::BlockQuote { data } => {
+                out.push_str("<blockquote>\n");
+                for block in data {
+                    self.render_block(block, out);
+                }
+                out.push_str("</blockquote>\n");
+            }
+            MdBlock::Alert { kind, title, data } => {
+                let name = kind.name();
+                out.push_str(&format!(
+                    "<div class=\"markdown-alert markdown-alert-{}\">\n",
+                    name.to_lowercase()
+                ));
+                out.push_str("<p class=\"markdown-alert-title\">");
+                match title {
+                    Some(title) => self.render_inline(title, out),
+                    None => out.push_str(name),
+                }
+                out.push_str("</p>\n");
+                for block in data {
+                    self.render_block(block, out);
+                }
+                out.push_str("</div>\n");
+            }
+            MdBlock::OList { data } => self.render_list("ol", data, out),
+            MdBlock::UList { data } => self.render_list("ul", data, out),
+            MdBlock::Image {
+                alt_text,
+                link_text,
+                title,
+                caption,
+                attrs,
+            } => {
+                let img = render_img(link_text, alt_text, title.as_deref(), attrs);
+                match caption {
+                    Some(caption) => {
+                        out.push_str(&format!("<figure>\n{}\n<figcaption>", img));
+                        self.render_inline(caption, out);
+                        out.push_str("</figcaption>\n</figure>\n");
+                    }
+                    None => out.push_str(&format!("<p>{}</p>\n", img)),
+                }
+            }
+            MdBlock::Table { data } => self.render_table(data, out),
+            MdBlock::LineBlock(data) => {
+                out.push_str("<div class=\"line-block\">");
+                for (i, line) in data.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str("<br />\n");
+                    }
+                    self.render_inline(line, out);
+                }
+                out.push_str("</div>\n");
+            }
+            MdBlock::Definition { data } => self.render_definitions(data, out),
+            MdBlock::CodeBlock { lang, attrs, code } => {
+                // `start` is the first line number, `<pre>` has no such attribute
+                let mut pre_attrs = attrs.clone();
+                for (key, _) in &mut pre_attrs.pairs {
+                    if key == "start" {
+                        *key = "data-start".to_string();
+                    }
+                }
+                out.push_str(&format!("<pre{}><code", render_attributes(&pre_attrs, None)));
+                if let Some(lang) = lang {
+                    out.push_str(&format!(" class=\"language-{}\"", escape_html(lang)));
+                }
+                out.push('>');
+                self.render_code(lang.as_deref(), attrs, code, out);
+                out.push_str("</code></pre>\n");
+            }
+            MdBlock::Diagram {
+                kind,
+                attrs,
+                source,
+            } => {
+                let rendered = self
+                    .diagram_renderer
+                    .as_ref()
+                    .and_then(|renderer| renderer.render(*kind, source));
+                match rendered {
+                    Some(html) => {
+                        out.push_str(&html);
+                        if !html.ends_with('\n') {
+                            out.push('\n');
+                        }
+                    }
+                    None => out.push_str(&format!(
+                        "<pre{}>{}</pre>\n",
+                        render_attributes(attrs, Some(kind.name())),
+                        escape_html(source)
+                    )),
+                }
+            }
+            MdBlock::Math(tex) => {
+                out.push_str("<p>");
+                render_math(tex, true, out);
+                out.push_str("</p>\n");
+            }
+            MdBlock::Container {
+                name,
+                attrs,
+                children,
+            } => {
+                out.push_str(&format!("<div{}>\n", render_attributes(attrs, Some(name))));
+                // `::: note "Title"`
+                if let Some(title) = attrs.args.first() {
+                    out.push_str(&format!(
+                        "<p class=\"container-title\">{}</p>\n",
+                        escape_html(title)
+                    ));
+                }
+                for block in children {
+                    self.render_block(block, out);
+                }
+                out.push_str("</div>\n");
+            }
+            MdBlock::Parah(data) => {
+                out.push_str("<p>");
+                self.render_inline(data, out);
+                out.push_str("</p>\n");
+            }
+            MdBlock::Comment(raw) => {
+                if self.comments {
+                    out.push_str(&html_comment(raw));
+                    out.push('\n');
+                }
+            }
+            // a file which couldn't be included has nothing to show
+            MdBlock::Include { .. } => (),
+            MdBlock::HR => out.push_str("<hr />\n"),
+            MdBlock::EmptyLine => (),
+        }
+    }
+
+    ///
+    /// highlighted code, with every line in `<span class="line">`
+    /// when lines are numbered or marked.
+    ///
+    fn render_code(&self, lang: Option<&str>, attrs: &Attributes, code: &str, out: &mut String) {
+        if code.is_empty() {
+            return;
+        }
+        let tokens = lang
+            .filter(|_| self.highlighting)
+            .and_then(|lang| highlight(lang, code))
+            .unwrap_or_else(|| vec![(TokenKind::Plain, code)]);
+        let first_number = first_line_number(attrs);
+        let marked = marked_lines(attrs);
+        if first_number.is_none() && marked.is_empty() {
+            render_tokens(&tokens, out);
+            out.push('\n');
+            return;
+        }
+        for (i, line) in token_lines(&tokens).iter().enumerate() {
+            let number = first_number.unwrap_or(1) + i;
+            if marked.iter().any(|range| range.contains(&number)) {
+                out.push_str("<span class=\"line marked\">");
+            } else {
+                out.push_str("<span class=\"line\">");
+            }
+            if first_number.is_some() {
+                out.push_str(&format!("<span class=\"line-number\">{}</span>", number));
+            }
+            render_tokens(line, out);
+            out.push_str("</span>\n");
+        }
+    }
+
+    fn render_list(&self, tag: &str, items: &[ListItem], out: &mut String) {
+        out.push_str(&format!("<{}>\n", tag));
+        for item in items {
+            match item.task {
+                Some(done) => {
+                    out.push_str("<li class=\"task-list-item\">");
+                    out.push_str("<input type=\"checkbox\" class=\"task-list-item-checkbox\" disabled=\"\"");
+                    if done {
+                        out.push_str(" checked=\"\"");
+                    }
+                    out.push_str(" /> ");
+                }
+                None => out.push_str("<li>"),
+            }
+            self.render_inline(&item.data, out);
+            if !item.children.is_empty() {
+                out.push('\n');
+                for block in &item.children {
+                    self.render_block(block, out);
+                }
+            }
+            out.push_str("</li>\n");
+        }
+        out.push_str(&format!("</{}>\n", tag));
+    }
+
+    fn render_table(&self, rows: &[(TableRow, Vec<MdString>)], out: &mut String) {
+        out.push_str("<table>\n");
+        let mut in_body = false;
+        for (kind, cells) in rows {
+            let tag = match kind {
+                TableRow::Heading => {
+                    out.push_str("<thead>\n");
+                    "th"
+                }
+                TableRow::Data => {
+                    if !in_body {
+                        out.push_str("<tbody>\n");
+                        in_body = true;
+                    }
+                    "td"
+                }
+            };
+            out.push_str("<tr>\n");
+            for cell in cells {
+                out.push_str(&format!("<{}>", tag));
+                self.render_inline(cell, out);
+                out.push_str(&format!("</{}>\n", tag));
+            }
+            out.push_str("</tr>\n");
+            if let TableRow::Heading = kind {
+                out.push_str("</thead>\n");
+            }
+        }
+        if in_body {
+            out.push_str("</tbody>\n");
+        }
+        out.push_str("</table>\n");
+    }
+
+    fn render_definitions(&self, items: &[DefinitionItem], out: &mut String) {
+        out.push_str("<dl>\n");
+        for item in items {
+            for term in &item.terms {
+                out.push_str("<dt>");
+                self.render_inline(term, out);
+                out.push_str("</dt>\n");
+            }
+            for def in &item.defs {
+                out.push_str("<dd>");
+                // a single paragraph is written without `<p>`
+                if let [MdBlock::Parah(data)] = def.as_slice() {
+                    self.render_inline(data, out);
+                } else {
+                    out.push('\n');
+                    for block in def {
+                        self.render_block(block, out);
+                    }
+                }
+                out.push_str("</dd>\n");
+            }
+        }
+        out.push_str("</dl>\n");
+    }
+
+    fn render_inline(&self, md_string: &MdString, out: &mut String) {
+        let mut inlines = md_string.iter().peekable();
+        while let Some(inline) = inlines.next() {
+            match inline {
+                MdInline::Bold(s) => self.render_tag("strong", s, out),
+                MdInline::Italic(s) => self.render_tag("em", s, out),
+                MdInline::BoldItalic(s) => {
+                    out.push_str("<em>");
+                    self.render_tag("strong", s, out);
+                    out.push_str("</em>");
+                }
+                MdInline::Code(s) => {
+                    out.push_str("<code>");
+                    out.push_str(&escape_html(&plain_text(s)));
+                    out.push_str("</code>");
+                }
+                MdInline::Strike(s) => self.render_tag("del", s, out),
+                MdInline::Highlight(s) => self.render_tag("mark", s, out),
+                MdInline::Sub(s) => self.render_tag("sub", s, out),
+                MdInline::Super(s) => self.render_tag("sup", s, out),
+                MdInline::LinkText(text) => {
+                    // `[text](url)` comes as two inlines
+                    if let Some(MdInline::LinkUrl(url)) = inlines.peek() {
+                        let url = plain_text(url);
+                        inlines.next();
+                        // `[text](url){.class}`
+                        let attrs = inlines
+                            .next_if(|inline| matches!(inline, MdInline::Attributes(_)));
+                        let attrs = match attrs {
+                            Some(MdInline::Attributes(attrs)) => render_attributes(attrs, None),
+                            _ => String::new(),
+                        };
+                        out.push_str(&format!("<a href=\"{}\"{}>", escape_html(url.trim()), attrs));
+                        self.render_inline(text, out);
+                        out.push_str("</a>");
+                    } else if let Some(MdInline::Attributes(attrs)) = inlines.peek() {
+                        // `[text]{.class}` is a span
+                        out.push_str(&format!("<span{}>", render_attributes(attrs, None)));
+                        inlines.next();
+                        self.render_inline(text, out);
+                        out.push_str("</span>");
+                    } else {
+                        out.push('[');
+                        self.render_inline(text, out);
+                        out.push(']');
+                    }
+                }
+                MdInline::Image {
+                    alt,
+                    url,
+                    title,
+                    attrs,
+                } => out.push_str(&render_img(url, &plain_text(alt), title.as_deref(), attrs)),
+                MdInline::LinkUrl(url) => {
+                    out.push('(');
+                    out.push_str(&escape_html(&plain_text(url)));
+                    out.push(')');
+                }
+                MdInline::Footnote(label) => {
+                    let label = escape_html(&plain_text(label));
+                    out.push_str(&format!(
+                        "<sup class=\"footnote-ref\"><a href=\"#fn-{0}\">{0}</a></sup>",
+                        label
+                    ));
+                }
+                MdInline::WikiLink {
+                    target,
+                    anchor,
+                    label,
+                    embed,
+                    attrs,
+                } => {
+                    let (anchor, label) = (anchor.as_deref(), label.as_deref());
+                    self.render_wiki_link(target, anchor, label, *embed, attrs, out)
+                }
+                // nothing to put them on
+                MdInline::Attributes(attrs) => {
+                    out.push_str(&escape_html(&format!("{{{}}}", attrs)))
+                }
+                MdInline::Math(tex) => render_math(tex, false, out),
+                MdInline::DisplayMath(tex) => render_math(tex, true, out),
+                MdInline::Emoji { name, emoji } => match &self.emoji_style {
+                    EmojiStyle::Unicode => out.push_str(emoji),
+                    EmojiStyle::Span => out.push_str(&format!(
+                        "<span class=\"emoji\" title=\":{}:\">{}</span>",
+                        escape_html(name),
+                        emoji
+                    )),
+                    EmojiStyle::Image(base) => out.push_str(&format!(
+                        "<img class=\"emoji\" src=\"{}{}.png\" alt=\"{}\" title=\":{}:\" />",
+                        escape_html(base),
+                        escape_html(name),
+                        emoji,
+                        escape_html(name)
+                    )),
+                },
+                MdInline::Abbr { text, title } => out.push_str(&format!(
+                    "<abbr title=\"{}\">{}</abbr>",
+                    escape_html(title),
+                    escape_html(text)
+                )),
+                MdInline::Reference(reference) => self.render_reference(reference, out),
+                MdInline::Comment(raw) => {
+                    if self.comments {
+                        out.push_str(&html_comment(raw));
+                    }
+                }
+                MdInline::SoftBreak => out.push('\n'),
+                MdInline::HardBreak => out.push_str("<br />\n"),
+                MdInline::InlineString(s) => out.push_str(&escape_html(s)),
+            }
+        }
+    }
+
+    fn render_wiki_link(
+        &self,
+        target: &str,
+        anchor: Option<&str>,
+        label: Option<&str>,
+        embed: bool,
+        attrs: &Attributes,
+        out: &mut String,
+    ) {
+        let text = wiki_text(target, anchor, label);
+        let Some(url) = self.wiki_resolver.resolve(target, anchor) else {
+            out.push_str(&format!(
+                "<span class=\"wikilink wikilink-broken\">{}</span>",
+                escape_html(&text)
+            ));
+            return;
+        };
+        if embed && is_image(target) {
+            out.push_str(&render_img(&url, &text, None, attrs));
+            return;
+        }
+        let (url, text) = (escape_html(&url), escape_html(&text));
+        if embed {
+            out.push_str(&format!(
+                "<a href=\"{}\" class=\"wikilink wikilink-embed\">{}</a>",
+                url, text
+            ));
+        } else {
+            out.push_str(&format!("<a href=\"{}\" class=\"wikilink\">{}</a>", url, text));
+        }
+    }
+
+    fn render_reference(&self, reference: &Reference, out: &mut String) {
+        let class = match reference {
+            Reference::Mention(_) => "mention",
+            Reference::Issue { .. } => "issue-ref",
+            Reference::Hashtag(_) => "hashtag",
+        };
+        let text = escape_html(&reference.to_string());
+        let url = self
+            .reference_resolver
+            .as_ref()
+            .and_then(|resolver| resolver.resolve(reference));
+        match url {
+            Some(url) => out.push_str(&format!(
+                "<a href=\"{}\" class=\"{}\">{}</a>",
+                escape_html(&url),
+                class,
+                text
+            )),
+            None => out.push_str(&format!("<span class=\"{}\">{}</span>", class, text)),
+        }
+    }
+
+    fn render_tag(&self, tag: &str, md_string: &MdString, out: &mut String) {
+        out.push_str(&format!("<{}>", tag));
+        self.render_inline(md_string, out);
+        out.push_str(&format!("</{}>", tag));
+    }
+}
+
+fn render_img(src: &str, alt: &str, title: Option<&str>, attrs: &Attributes) -> String {
+    let title = title
+        .map(|title| format!(" title=\"{}\"", escape_html(title)))
+        .unwrap_or_default();
+    format!(
+        "<img src=\"{}\" alt=\"{}\"{}{} />",
+        escape_html(src),
+        escape_html(alt),
+        title,
+        render_attributes(attrs, None)
+    )
+}
+
+/// highlighted code as `<span class="hl-...">`, plain text as it is
+fn render_tokens(tokens: &[(TokenKind, &str)], out: &mut String) {
+    for (kind, text) in tokens {
+        match kind.class() {
+            Some(class) => {
+                out.push_str(&format!("<span class=\"{}\">{}</span>", class, escape_html(text)))
+            }
+            None => out.push_str(&escape_html(text)),
+        }
+    }
+}
+
+/// `%% note %%` becomes `<!-- note -->`, which can't be closed early by a `-->` in it
+fn html_comment(raw: &str) -> String {
+    let inner = raw
+        .strip_prefix("<!--")
+        .and_then(|raw| raw.strip_suffix("-->"))
+        .or_else(|| raw.strip_prefix("%%").and_then(|raw| raw.strip_suffix("%%")))
+        .unwrap_or(raw);
+    format!("<!--{}-->", inner.replace("-->", "--&gt;"))
+}
+
+///
+/// math is left for a script like MathJax or KaTeX,
+/// in their `\(...\)` and `\[...\]` delimiters.
+///
+fn render_math(tex: &str, display: bool, out: &mut String) {
+    let (class, open, close) = if display {
+        ("display", "\\[", "\\]")
+    } else {
+        ("inline", "\\(", "\\)")
+    };
+    out.push_str(&format!(
+        "<span class=\"math {}\">{}{}{}</span>",
+        class,
+        open,
+        escape_html(tex),
+        close
+    ));
+}
+
+/// text shown for a wiki link, its label or else the link itself
+pub(crate) fn wiki_text(target: &str, anchor: Option<&str>, label: Option<&str>) -> String {
+    match (label, anchor) {
+        (Some(label), _) => label.to_string(),
+        (None, Some(anchor)) if target.is_empty() => anchor.to_string(),
+        (None, Some(anchor)) => format!("{}#{}", target, anchor),
+        (None, None) => target.to_string(),
+    }
+}
+
+fn is_image(target: &str) -> bool {
+    let ext = target.rsplit_once('.').map_or("", |(_, ext)| ext);
+    ["png", "jpg", "jpeg", "gif", "svg", "webp", "bmp"]
+        .iter()
+        .any(|image| ext.eq_ignore_ascii_case(image))
+}
+
+///
+/// `attrs` as html attributes with a space before each,
+/// `class` goes before the other classes. args are left out.
+///
+fn render_attributes(attrs: &Attributes, class: Option<&str>) -> String {
+    let mut out = String::new();
+    if let Some(id) = &attrs.id {
+        out.push_str(&format!(" id=\"{}\"", escape_html(id)));
+    }
+    let classes = class
+        .filter(|class| !class.is_empty())
+        .into_iter()
+        .chain(attrs.classes.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+    if !classes.is_empty() {
+        out.push_str(&format!(" class=\"{}\"", escape_html(&classes.join(" "))));
+    }
+    for (key, value) in &attrs.pairs {
+        out.push_str(&format!(" {}=\"{}\"", escape_html(key), escape_html(value)));
+    }
+    out
+}
+
+pub(crate) fn escape_html(data: &str) -> String {
+    let mut escaped = String::with_capacity(data.len());
+    for ch in data.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// text of `md_string` without any formatting
+pub(crate) fn plain_text(md_string: &MdString) -> String {
+    let mut text = String::new();
+    push_plain_text(md_string.iter(), &mut text);
+    text
+}
+
+fn push_plain_text(inlines: Iter<'_, MdInline>, text: &mut String) {
+    for inline in inlines {
+        match inline {
+            MdInline::Bold(s)
+            | MdInline::Italic(s)
+            | MdInline::BoldItalic(s)
+            | MdInline::Code(s)
+            | MdInline::Strike(s)
+            | MdInline::Highlight(s)
+            | MdInline::Sub(s)
+            | MdInline::Super(s)
+            | MdInline::LinkText(s)
+            | MdInline::Footnote(s)
+            | MdInline::Image { alt: s, .. } => push_plain_text(s.iter(), text),
+            // url isn't part of the text
+            MdInline::LinkUrl(_) | MdInline::Attributes(_) | MdInline::Comment(_) => (),
+            MdInline::WikiLink {
+                target,
+                anchor,
+                label,
+                ..
+            } => text.push_str(&wiki_text(target, anchor.as_deref(), label.as_deref())),
+            MdInline::Math(tex) | MdInline::DisplayMath(tex) => text.push_str(tex),
+            MdInline::Emoji { emoji, .. } => text.push_str(emoji),
+            MdInline::Abbr { text: abbr, .. } => text.push_str(abbr),
+            MdInline::Reference(reference) => text.push_str(&reference.to_string()),
+            MdInline::SoftBreak | MdInline::HardBreak => text.push(' '),
+            MdInline::InlineString(s) => text.push_str(s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod html_testing {
+    use super::{EmojiStyle, HtmlRenderer, Reference};
+    use crate::{md_to_html, parser_md, parser_md_with_options, DiagramKind, ParserOptions};
+
+    #[test]
+    fn paragraph_html() {
+        assert_eq!(
+            md_to_html("**a** <b>\\\nc [link](u?a=1&b=2)".to_string()),
+            "<p><strong>a</strong> &lt;b&gt;<br />\nc <a href=\"u?a=1&amp;b=2\">link</a></p>\n"
+        );
+        assert_eq!(
+            md_to_html("[a](u?a=1&amp;b=2&#x41;)".to_string()),
+            "<p><a href=\"u?a=1&amp;b=2A\">a</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn unmatched_brackets_html() {
+        // brackets are written as they were, with nothing added or dropped
+        assert_eq!(
+            md_to_html("[text *a* (url **b** [^c\nd".to_string()),
+            "<p>[text <em>a</em> (url <strong>b</strong> [^c\nd</p>\n"
+        );
+        assert_eq!(md_to_html("a ] b ) c".to_string()), "<p>a ] b ) c</p>\n");
+    }
+
+    #[test]
+    fn front_matter_not_rendered() {
+        assert_eq!(
+            md_to_html("---\ntitle: x\n---\n---\n".to_string()),
+            "<hr />\n"
+        );
+        let options = ParserOptions::commonmark();
+        let document = parser_md_with_options("---\ntitle: x\n---\n".to_string(), &options);
+        assert!(document.front_matter.is_none());
+        assert!(HtmlRenderer::new().render(&document.blocks).contains("title: x"));
+    }
+
+    #[test]
+    fn task_list_html() {
+        assert_eq!(
+            md_to_html("- [x] done\n  - [ ] todo".to_string()),
+            "<ul>\n<li class=\"task-list-item\"><input type=\"checkbox\" \
+             class=\"task-list-item-checkbox\" disabled=\"\" checked=\"\" /> done\n\
+             <ul>\n<li class=\"task-list-item\"><input type=\"checkbox\" \
+             class=\"task-list-item-checkbox\" disabled=\"\" /> todo</li>\n</ul>\n\
+             </li>\n</ul>\n"
+        );
+    }
+
+    #[test]
+    fn math_html() {
+        assert_eq!(
+            md_to_html("$a<b$ costs $5\n$$\nx_1 *\n$$".to_string()),
+            "<p><span class=\"math inline\">\\(a&lt;b\\)</span> costs $5</p>\n\
+             <p><span class=\"math display\">\\[x_1 *\\]</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn alert_html() {
+        assert_eq!(
+            md_to_html("> [!WARNING]\n> be *careful*\n\n> [!tip] Use this\n> - one".to_string()),
+            "<div class=\"markdown-alert markdown-alert-warning\">\n\
+             <p class=\"markdown-alert-title\">Warning</p>\n\
+             <p>be <em>careful</em></p>\n</div>\n\
+             <div class=\"markdown-alert markdown-alert-tip\">\n\
+             <p class=\"markdown-alert-title\">Use this</p>\n\
+             <ul>\n<li>one</li>\n</ul>\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn container_html() {
+        assert_eq!(
+            md_to_html("::: warning #w .big data-x=1 \"Title\"\n*a*\n:::".to_string()),
+            "<div id=\"w\" class=\"warning big\" data-x=\"1\">\n\
+             <p class=\"container-title\">Title</p>\n<p><em>a</em></p>\n</div>\n"
+        );
+        assert_eq!(
+            md_to_html("::: note\na\n:::".to_string()),
+            "<div class=\"note\">\n<p>a</p>\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn attributes_html() {
+        assert_eq!(
+            md_to_html(
+                "# Head {#top .big}\n\
+                 ```rust {.numbers start=3}\nfn a() {}\n```\n\
+                 ![a](b.png){width=50%}\n\
+                 [link](u){target=_blank} and [span]{.red} {.no}"
+                    .to_string()
+            ),
+            "<h1 id=\"top\" class=\"big\">Head</h1>\n\
+             <pre class=\"numbers\" data-start=\"3\"><code class=\"language-rust\">\
+             <span class=\"line\"><span class=\"line-number\">3</span>\
+             <span class=\"hl-keyword\">fn</span> <span class=\"hl-function\">a</span>() {}\
+             </span>\n</code></pre>\n\
+             <p><img src=\"b.png\" alt=\"a\" width=\"50%\" /></p>\n\
+             <p><a href=\"u\" target=\"_blank\">link</a> and <span class=\"red\">span</span> {.no}</p>\n"
+        );
+    }
+
+    #[test]
+    fn figure_html() {
+        assert_eq!(
+            md_to_html(
+                "![a cat](cat.png \"The cat\" =640x480)\n\n\
+                 ![dog](dog.png)\n*A **good** dog*\n\n\
+                 See ![icon](i.png =16x16) here"
+                    .to_string()
+            ),
+            "<figure>\n<img src=\"cat.png\" alt=\"a cat\" title=\"The cat\" \
+             width=\"640\" height=\"480\" />\n<figcaption>The cat</figcaption>\n</figure>\n\
+             <figure>\n<img src=\"dog.png\" alt=\"dog\" />\n\
+             <figcaption>A <strong>good</strong> dog</figcaption>\n</figure>\n\
+             <p>See <img src=\"i.png\" alt=\"icon\" width=\"16\" height=\"16\" /> here</p>\n"
+        );
+    }
+
+    #[test]
+    fn diagram_html() {
+        let data = "```mermaid\ngraph TD\n  A --> B\n```\n```dot {.small}\ndigraph { a -> b }\n```";
+        assert_eq!(
+            md_to_html(data.to_string()),
+            "<pre class=\"mermaid\">graph TD\n  A --&gt; B</pre>\n\
+             <pre class=\"dot small\">digraph { a -&gt; b }</pre>\n"
+        );
+
+        let renderer = HtmlRenderer::new().with_diagram_renderer(|kind: DiagramKind, _: &str| {
+            (kind == DiagramKind::Dot).then(|| "<svg></svg>".to_string())
+        });
+        assert_eq!(
+            renderer.render(&parser_md(data.to_string()).blocks),
+            "<pre class=\"mermaid\">graph TD\n  A --&gt; B</pre>\n<svg></svg>\n"
+        );
+    }
+
+    #[test]
+    fn wiki_link_html() {
+        let data = "[[Home]] [[My Page#Intro|intro]] [[#Top]] ![[cat.png|200]] [[Missing]]";
+        assert_eq!(
+            md_to_html(data.to_string()),
+            "<p><a href=\"Home\" class=\"wikilink\">Home</a> \
+             <a href=\"My%20Page#Intro\" class=\"wikilink\">intro</a> \
+             <a href=\"#Top\" class=\"wikilink\">Top</a> \
+             <img src=\"cat.png\" alt=\"cat.png\" width=\"200\" /> \
+             <a href=\"Missing\" class=\"wikilink\">Missing</a></p>\n"
+        );
+
+        let renderer = HtmlRenderer::new().with_wiki_resolver(|target: &str, _: Option<&str>| {
+            (target != "Missing").then(|| format!("/wiki/{}", target.to_lowercase()))
+        });
+        assert_eq!(
+            renderer.render(&parser_md("[[Home]] [[Missing]]".to_string()).blocks),
+            "<p><a href=\"/wiki/home\" class=\"wikilink\">Home</a> \
+             <span class=\"wikilink wikilink-broken\">Missing</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn abbreviation_html() {
+        let data = "*[W3C]: World Wide Web \"Consortium\"\nThe W3C specs";
+        assert_eq!(
+            md_to_html(data.to_string()),
+            "<p>The <abbr title=\"World Wide Web &quot;Consortium&quot;\">W3C</abbr> specs</p>\n"
+        );
+    }
+
+    #[test]
+    fn reference_html() {
+        let options = ParserOptions {
+            mentions: true,
+            issue_refs: true,
+            hashtags: true,
+            ..ParserOptions::default()
+        };
+        let blocks = parser_md_with_options("@ana fixed #7 in a/b#8 #done".to_string(), &options);
+        let renderer = HtmlRenderer::new().with_reference_resolver(|reference: &Reference| {
+            match reference {
+                Reference::Mention(user) => Some(format!("/users/{}", user)),
+                Reference::Issue { repo: None, number } => Some(format!("/issues/{}", number)),
+                _ => None,
+            }
+        });
+        assert_eq!(
+            renderer.render(&blocks.blocks),
+            "<p><a href=\"/users/ana\" class=\"mention\">@ana</a> fixed \
+             <a href=\"/issues/7\" class=\"issue-ref\">#7</a> in \
+             <span class=\"issue-ref\">a/b#8</span> <span class=\"hashtag\">#done</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn emoji_html() {
+        let data = "Ship it :rocket: :nope: `:tada:`";
+        assert_eq!(
+            md_to_html(data.to_string()),
+            "<p>Ship it \u{1f680} :nope: <code>:tada:</code></p>\n"
+        );
+
+        let blocks = parser_md(":tada:".to_string()).blocks;
+        assert_eq!(
+            HtmlRenderer::new().with_emoji_style(EmojiStyle::Span).render(&blocks),
+            "<p><span class=\"emoji\" title=\":tada:\">\u{1f389}</span></p>\n"
+        );
+        let renderer = HtmlRenderer::new().with_emoji_style(EmojiStyle::Image("/e/".to_string()));
+        assert_eq!(
+            renderer.render(&blocks),
+            "<p><img class=\"emoji\" src=\"/e/tada.png\" alt=\"\u{1f389}\" \
+             title=\":tada:\" /></p>\n"
+        );
+    }
+
+    #[test]
+    fn definition_html() {
+        assert_eq!(
+            md_to_html("Term *1*\nTerm 2\n: def 1\n\n: def 2\n\n    more\n".to_string()),
+            "<dl>\n<dt>Term <em>1</em></dt>\n<dt>Term 2</dt>\n<dd>def 1</dd>\n\
+             <dd>\n<p>def 2</p>\n<p>more</p>\n</dd>\n</dl>\n"
+        );
+    }
+
+    #[test]
+    fn comment_html() {
+        let data = "<!-- draft\n*not* here -->\n\
+                    Some *text <!-- a * --> here* %% note %%\n\n\
+                    %% hidden %%";
+        assert_eq!(md_to_html(data.to_string()), "<p>Some <em>text  here</em> </p>\n");
+        let blocks = parser_md(data.to_string()).blocks;
+        assert_eq!(
+            HtmlRenderer::new().with_comments(true).render(&blocks),
+            "<!-- draft\n*not* here -->\n\
+             <p>Some <em>text <!-- a * --> here</em> <!-- note --></p>\n\
+             <!-- hidden -->\n"
+        );
+        assert_eq!(
+            md_to_html("<!-- a\nb --> after *it*".to_string()),
+            "<p>after <em>it</em></p>\n"
+        );
+    }
+
+    #[test]
+    fn line_block_html() {
+        assert_eq!(
+            md_to_html("| Roses\n|   are *red*\n|".to_string()),
+            "<div class=\"line-block\">Roses<br />\n\u{a0}\u{a0}are <em>red</em><br />\n</div>\n"
+        );
+    }
+}
@@ -0,0 +1,493 @@
+use std::cell::RefCell;
+
+use crate::{
+    md_block::{DefinitionItem, ListItem, MdBlock, TableRow},
+    md_inline_parser::{
+        emoji::emoji, entities::decode_entity, md_inline::MdInline, md_string::MdString,
+    },
+    md_line_reader::{to_mdline, MdRawLine},
+};
+
+///
+/// writes `MdBlock`s back as markdown
+///
+/// text is escaped wherever it would be read as markup,
+/// code and math are written as they are, abbreviations
+/// are defined again at the end.
+///
+pub struct MarkdownWriter {
+    /// abbreviations met while writing, with their titles
+    abbrs: RefCell<Vec<(String, String)>>,
+}
+
+impl Default for MarkdownWriter {
+    fn default() -> Self {
+        MarkdownWriter::new()
+    }
+}
+
+impl MarkdownWriter {
+    pub fn new() -> Self {
+        MarkdownWriter {
+            abbrs: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn render(&self, blocks: &[MdBlock]) -> String {
+        let mut out = self.render_blocks(blocks);
+        let abbrs = self.abbrs.take();
+        if !abbrs.is_empty() {
+            out.push_str("\n\n");
+            let defs = abbrs
+                .iter()
+                .map(|(abbr, title)| format!("*[{}]: {}", abbr, title))
+                .collect::<Vec<_>>();
+            out.push_str(&defs.join("\n"));
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    /// blocks separated by a blank line, without a line ending at the end
+    fn render_blocks(&self, blocks: &[MdBlock]) -> String {
+        blocks
+            .iter()
+            .filter(|block| !matches!(block, MdBlock::EmptyLine))
+            .map(|block| self.render_block(block))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn render_block(&self, block: &MdBlock) -> String {
+        match block {
+            MdBlock::Head { level, attrs, data } => {
+                let mut out = format!("{} {}", "#".repeat(*level as usize), self.render_inline(data));
+                if !attrs.is_empty() {
+                    out.push_str(&format!(" {{{}}}", attrs));
+                }
+                out
+            }
+            MdBlock::BlockQuote { data } => quote(&self.render_blocks(data)),
+            MdBlock::Alert { kind, title, data } => {
+                let mut out = format!("[!{}]", kind.name().to_uppercase());
+                if let Some(title) = title {
+                    out.push(' ');
+                    out.push_str(&self.render_inline(title));
+                }
+                if !data.is_empty() {
+                    out.push('\n');
+                    out.push_str(&self.render_blocks(data));
+                }
+                quote(&out)
+            }
+            MdBlock::OList { data } => self.render_list(true, data),
+            MdBlock::UList { data } => self.render_list(false, data),
+            MdBlock::Image {
+                alt_text,
+                link_text,
+                title,
+                caption,
+                attrs,
+            } => {
+                let mut out = format!("![{}]({}{})", alt_text, link_text, link_title(title));
+                if !attrs.is_empty() {
+                    out.push_str(&format!("{{{}}}", attrs));
+                }
+                // a caption which isn't the title is the italic line after
+                let from_title = title.clone().map(MdString::from_string);
+                if let Some(caption) = caption.as_ref().filter(|_| *caption != from_title) {
+                    out.push_str(&format!("\n*{}*", self.render_inline(caption)));
+                }
+                out
+            }
+            MdBlock::Table { data } => self.render_table(data),
+            MdBlock::LineBlock(data) => data
+                .iter()
+                .map(|line| self.render_line_block_line(line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            MdBlock::Definition { data } => self.render_definitions(data),
+            MdBlock::CodeBlock { lang, attrs, code } => {
+                let mut out = format!("```{}", lang.as_deref().unwrap_or_default());
+                if !attrs.is_empty() {
+                    out.push_str(&format!(" {{{}}}", attrs));
+                }
+                format!("{}\n{}\n```", out, code)
+            }
+            MdBlock::Diagram {
+                kind,
+                attrs,
+                source,
+            } => {
+                let mut out = format!("```{}", kind.name());
+                if !attrs.is_empty() {
+                    out.push_str(&format!(" {{{}}}", attrs));
+                }
+                format!("{}\n{}\n```", out, source)
+            }
+            MdBlock::Math(tex) => format!("$$\n{}\n$$", tex),
+            MdBlock::Comment(raw) => raw.clone(),
+            MdBlock::Include { path, attrs } if attrs.is_empty() => {
+                format!("{{{{#include {}}}}}", path)
+            }
+            MdBlock::Include { path, attrs } => format!("{{{{#include {} {}}}}}", path, attrs),
+            MdBlock::Container {
+                name,
+                attrs,
+                children,
+            } => {
+                let mut out = match (name.is_empty(), attrs.is_empty()) {
+                    (false, true) => format!("::: {}", name),
+                    (false, false) => format!("::: {} {}", name, attrs),
+                    (true, _) => format!("::: {{{}}}", attrs),
+                };
+                out.push('\n');
+                if !children.is_empty() {
+                    out.push_str(&self.render_blocks(children));
+                    out.push('\n');
+                }
+                out.push_str(":::");
+                out
+            }
+            MdBlock::Parah(data) => self.render_parah(data),
+            MdBlock::HR => "---".to_string(),
+            MdBlock::EmptyLine => String::new(),
+        }
+    }
+
+    fn render_list(&self, ordered: bool, items: &[ListItem]) -> String {
+        let mut lines = vec![];
+        for (i, item) in items.iter().enumerate() {
+            let marker = if ordered {
+                format!("{}. ", i + 1)
+            } else {
+                "- ".to_string()
+            };
+            let task = match item.task {
+                Some(true) => "[x] ",
+                Some(false) => "[ ] ",
+                None => "",
+            };
+            lines.push(format!("{}{}{}", marker, task, self.render_inline(&item.data)));
+            // children go under the item text
+            let indent = " ".repeat(marker.len());
+            for line in self.render_blocks(&item.children).lines() {
+                if line.is_empty() {
+                    lines.push(String::new());
+                } else {
+                    lines.push(format!("{}{}", indent, line));
+                }
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn render_table(&self, rows: &[(TableRow, Vec<MdString>)]) -> String {
+        let mut lines = vec![];
+        for (kind, cells) in rows {
+            let cells = cells
+                .iter()
+                .map(|cell| self.render_inline(cell).replace('|', "\\|"))
+                .collect::<Vec<_>>();
+            lines.push(format!("| {} |", cells.join(" | ")));
+            if let TableRow::Heading = kind {
+                lines.push(format!("|{}", " --- |".repeat(cells.len())));
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn render_line_block_line(&self, line: &MdString) -> String {
+        let mut text = self.render_inline(line);
+        if text.is_empty() {
+            return "|".to_string();
+        }
+        // `|` at the end would make it a table row
+        if text.ends_with('|') {
+            text.insert(text.len() - 1, '\\');
+        }
+        let rest = text.trim_start_matches('\u{a0}');
+        let indent = (text.len() - rest.len()) / '\u{a0}'.len_utf8();
+        format!("| {}{}", " ".repeat(indent), rest)
+    }
+
+    fn render_definitions(&self, items: &[DefinitionItem]) -> String {
+        let mut entries = vec![];
+        for item in items {
+            let mut lines = vec![];
+            for term in &item.terms {
+                lines.push(self.render_parah(term));
+            }
+            for def in &item.defs {
+                // first block goes after `: `, the rest are indented
+                for (i, block) in def.iter().enumerate() {
+                    let block = self.render_block(block);
+                    if i == 0 {
+                        lines.push(format!(": {}", block));
+                    } else {
+                        lines.push(String::new());
+                        lines.extend(block.lines().map(|line| format!("    {}", line)));
+                    }
+                }
+            }
+            entries.push(lines.join("\n"));
+        }
+        entries.join("\n\n")
+    }
+
+    ///
+    /// inlines of a paragraph, a line which would start some
+    /// other block (`# `, `- `, `1. ` ...) gets escaped.
+    ///
+    fn render_parah(&self, md_string: &MdString) -> String {
+        self.render_inline(md_string)
+            .split('\n')
+            .map(|line| match to_mdline(line.to_string()) {
+                MdRawLine::Text(_) | MdRawLine::EmptyLine => line.to_string(),
+                _ => escape_line_start(line),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn render_inline(&self, md_string: &MdString) -> String {
+        let mut out = String::new();
+        for inline in md_string.iter() {
+            match inline {
+                MdInline::Bold(s) => self.render_enclosed("**", s, &mut out),
+                MdInline::Italic(s) => self.render_enclosed("*", s, &mut out),
+                MdInline::BoldItalic(s) => self.render_enclosed("***", s, &mut out),
+                MdInline::Code(s) => out.push_str(&code_span(&raw_text(s))),
+                MdInline::Strike(s) => self.render_enclosed("~~", s, &mut out),
+                MdInline::Highlight(s) => self.render_enclosed("==", s, &mut out),
+                MdInline::Sub(s) => self.render_enclosed("~", s, &mut out),
+                MdInline::Super(s) => self.render_enclosed("^", s, &mut out),
+                MdInline::LinkText(s) => {
+                    out.push('[');
+                    out.push_str(&self.render_inline(s));
+                    out.push(']');
+                }
+                MdInline::Image {
+                    alt,
+                    url,
+                    title,
+                    attrs,
+                } => {
+                    // only `width` and `height` can be in here
+                    let size = match (attrs.get("width"), attrs.get("height")) {
+                        (None, None) => String::new(),
+                        (width, height) => {
+                            format!(" ={}x{}", width.unwrap_or(""), height.unwrap_or(""))
+                        }
+                    };
+                    out.push_str(&format!(
+                        "![{}]({}{}{})",
+                        self.render_inline(alt),
+                        url,
+                        link_title(title),
+                        size
+                    ));
+                }
+                // url and footnote label are read without any markup
+                MdInline::LinkUrl(s) => out.push_str(&format!("({})", raw_text(s))),
+                MdInline::Footnote(s) => out.push_str(&format!("[^{}]", raw_text(s))),
+                MdInline::WikiLink {
+                    target,
+                    anchor,
+                    label,
+                    embed,
+                    attrs,
+                } => {
+                    if *embed {
+                        out.push('!');
+                    }
+                    out.push_str("[[");
+                    out.push_str(target);
+                    if let Some(anchor) = anchor {
+                        out.push('#');
+                        out.push_str(anchor);
+                    }
+                    if let Some(label) = label {
+                        out.push('|');
+                        out.push_str(label);
+                    }
+                    if let Some(width) = attrs.get("width") {
+                        out.push('|');
+                        out.push_str(width);
+                        if let Some(height) = attrs.get("height") {
+                            out.push('x');
+                            out.push_str(height);
+                        }
+                    }
+                    out.push_str("]]");
+                }
+                MdInline::Attributes(attrs) => out.push_str(&format!("{{{}}}", attrs)),
+                MdInline::Math(tex) => out.push_str(&format!("${}$", tex)),
+                MdInline::DisplayMath(tex) => out.push_str(&format!("$${}$$", tex)),
+                MdInline::Emoji { name, .. } => out.push_str(&format!(":{}:", name)),
+                MdInline::Abbr { text, title } => {
+                    let mut abbrs = self.abbrs.borrow_mut();
+                    if !abbrs.iter().any(|(abbr, _)| abbr == text) {
+                        abbrs.push((text.clone(), title.clone()));
+                    }
+                    out.push_str(&escape_markdown(text));
+                }
+                MdInline::Reference(reference) => out.push_str(&reference.to_string()),
+                MdInline::Comment(raw) => out.push_str(raw),
+                MdInline::SoftBreak => out.push('\n'),
+                MdInline::HardBreak => out.push_str("\\\n"),
+                MdInline::InlineString(s) => out.push_str(&escape_markdown(s)),
+            }
+        }
+        out
+    }
+
+    fn render_enclosed(&self, delimiter: &str, md_string: &MdString, out: &mut String) {
+        out.push_str(delimiter);
+        out.push_str(&self.render_inline(md_string));
+        out.push_str(delimiter);
+    }
+}
+
+/// ` "title"` to go after a url, or nothing without a title
+fn link_title(title: &Option<String>) -> String {
+    match title {
+        Some(title) if title.contains('"') => format!(" '{}'", title),
+        Some(title) => format!(" \"{}\"", title),
+        None => String::new(),
+    }
+}
+
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("> {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// text of inlines which were read without markup, like a url
+fn raw_text(md_string: &MdString) -> String {
+    let mut text = String::new();
+    for inline in md_string.iter() {
+        if let MdInline::InlineString(s) = inline {
+            text.push_str(s);
+        }
+    }
+    text
+}
+
+///
+/// backtick run longer than any run inside `code`, with
+/// spaces around it when needed to keep `code` as it is.
+///
+fn code_span(code: &str) -> String {
+    let longest = code
+        .split(|ch| ch != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest + 1);
+    let padded = code.starts_with('`')
+        || code.ends_with('`')
+        || (code.starts_with(' ') && code.ends_with(' ') && !code.bytes().all(|b| b == b' '));
+    if padded {
+        format!("{0} {1} {0}", fence, code)
+    } else {
+        format!("{0}{1}{0}", fence, code)
+    }
+}
+
+/// backslash before every character the inline tokenizer would pick up
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut prev = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        let next = chars.peek().map(|&(_, next)| next);
+        let markup = match ch {
+            '\\' | '*' | '`' | '[' | ']' | '(' | ')' | '~' | '^' | '$' => true,
+            // a single `=` or `%` is just text
+            '=' => prev == Some('=') || next == Some('='),
+            '%' => next == Some('%'),
+            '<' => text[i..].starts_with("<!--"),
+            '&' => decode_entity(&text[i..]).is_some(),
+            ':' => is_shortcode(&text[i..]),
+            _ => false,
+        };
+        if markup {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+        prev = Some(ch);
+    }
+    escaped
+}
+
+/// `data` starts with a known `:shortcode:`
+fn is_shortcode(data: &str) -> bool {
+    data[1..]
+        .split_once(':')
+        .is_some_and(|(name, _)| emoji(name).is_some())
+}
+
+/// `line` starts with a block marker, which is made text
+fn escape_line_start(line: &str) -> String {
+    // `1. ` can't have its digit escaped, so `.` is
+    let at = line.bytes().take_while(u8::is_ascii_digit).count();
+    if line[at..].starts_with('\\') {
+        // already escaped by `escape_markdown`
+        return line.to_string();
+    }
+    format!("{}\\{}", &line[..at], &line[at..])
+}
+
+#[cfg(test)]
+mod markdown_testing {
+    use super::MarkdownWriter;
+    use crate::parser_md;
+
+    fn round_trip(data: &str) -> String {
+        MarkdownWriter::new().render(&parser_md(data.to_string()).blocks)
+    }
+
+    #[test]
+    fn math_verbatim() {
+        let data = "Euler $e^{i\\pi} + 1 = 0$ and $$\\sum_{i=1}^n *x_i*$$\n\n\
+                    $$\n\\frac{a_1}{b_2}\n$$\n";
+        assert_eq!(round_trip(data), data);
+    }
+
+    #[test]
+    fn escaped_text() {
+        let data = "\\# not \\*head\\* \\:tada: :tada: (x) a \\=\\= b \\&amp; 1 \\$\n\
+                    2\\. still text\n";
+        assert_eq!(round_trip(data), data);
+        assert_eq!(round_trip(&round_trip(data)), data);
+    }
+
+    #[test]
+    fn blocks_round_trip() {
+        let data = "## Title {#top}\n\n\
+                    > quoted **text** [[Page#Part|part]] ![[a.png|200x100]]\n\n\
+                    > [!NOTE] Read *this*\n> first\n\n\
+                    - [x] done\n  - child ``a`b``\n- ~~gone~~ ==mark==\n\n\
+                    1. [link](https://a.b/c){.ext} [span]{#s}\n\n\
+                    | a | b |\n| --- | --- |\n| 1 | c\\|d |\n\n\
+                    Term\n: def\n\n    more\n\n\
+                    ```rust {.numbers}\n*code*\n```\n\n\
+                    ::: tabs #t key=\"a b\"\n::: {.tab}\none\n:::\n:::\n\n\
+                    ---\n\n\
+                    ![a](a.png \"A\"){.wide}\n\n\
+                    ![b](b.png)\n*The b*\n\n\
+                    An ![*i*](i.png =16x) icon\n\n\
+                    ```mermaid {#flow}\ngraph TD\n  A --> B\n```\n\n\
+                    {{#include parts/a.md lines=1-3 section=\"Getting started\"}}\n\n\
+                    <!-- a\nnote -->\n\n\
+                    | Roses\n|   are *red* \\|\n|\n\n\
+                    Uses HTML %% hidden %% and 100\\%% \\<!--\n\n\
+                    *[HTML]: Hyper Text Markup Language\n";
+        assert_eq!(round_trip(data), data);
+    }
+}
@@ -0,0 +1,11 @@
+///
+/// After Blocks and Inlines are parsed we come here
+/// to write them out in some other format
+///
+pub mod diagram_renderer;
+pub mod highlighter;
+pub mod html_renderer;
+pub mod markdown_writer;
+pub mod reference_resolver;
+pub mod terminal_renderer;
+pub mod wiki_resolver;
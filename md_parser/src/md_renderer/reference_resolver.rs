@@ -0,0 +1,19 @@
+use crate::md_inline_parser::references::Reference;
+
+///
+/// maps a `Reference` to a url, at render time.
+///
+/// `None` leaves the reference unlinked.
+///
+pub trait ReferenceResolver {
+    fn resolve(&self, reference: &Reference) -> Option<String>;
+}
+
+impl<F> ReferenceResolver for F
+where
+    F: Fn(&Reference) -> Option<String>,
+{
+    fn resolve(&self, reference: &Reference) -> Option<String> {
+        self(reference)
+    }
+}
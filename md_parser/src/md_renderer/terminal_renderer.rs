@@ -0,0 +1,336 @@
+use crate::{
+    md_block::{DefinitionItem, ListItem, MdBlock, TableRow},
+    md_inline_parser::{md_inline::MdInline, md_string::MdString},
+};
+
+use super::{
+    highlighter::{first_line_number, highlight, marked_lines, token_lines, TokenKind},
+    html_renderer::{plain_text, wiki_text},
+};
+
+const RESET: &str = "\x1b[0m";
+
+///
+/// writes `MdBlock`s as text for a terminal, styled
+/// with ANSI escape codes, code blocks are highlighted.
+///
+pub struct TerminalRenderer {
+    comments: bool,
+}
+
+impl Default for TerminalRenderer {
+    fn default() -> Self {
+        TerminalRenderer::new()
+    }
+}
+
+impl TerminalRenderer {
+    pub fn new() -> Self {
+        TerminalRenderer { comments: false }
+    }
+
+    /// comments are shown dimmed, they are left out by default
+    pub fn with_comments(mut self, comments: bool) -> Self {
+        self.comments = comments;
+        self
+    }
+
+    pub fn render(&self, blocks: &[MdBlock]) -> String {
+        let mut out = self.render_blocks(blocks);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    /// blocks separated by a blank line, without a line ending at the end
+    fn render_blocks(&self, blocks: &[MdBlock]) -> String {
+        blocks
+            .iter()
+            .filter(|block| match block {
+                MdBlock::EmptyLine | MdBlock::Include { .. } => false,
+                MdBlock::Comment(_) => self.comments,
+                _ => true,
+            })
+            .map(|block| self.render_block(block))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn render_block(&self, block: &MdBlock) -> String {
+        match block {
+            MdBlock::Head { level, data, .. } => {
+                let hashes = "#".repeat(*level as usize);
+                style("1", &format!("{} {}", hashes, self.render_inline(data)))
+            }
+            MdBlock::BlockQuote { data } => indent(&self.render_blocks(data), "\u{2502} "),
+            MdBlock::Alert { kind, title, data } => {
+                let title = match title {
+                    Some(title) => self.render_inline(title),
+                    None => kind.name().to_string(),
+                };
+                let mut out = style("1", &title);
+                if !data.is_empty() {
+                    out.push('\n');
+                    out.push_str(&self.render_blocks(data));
+                }
+                indent(&out, "\u{2502} ")
+            }
+            MdBlock::OList { data } => self.render_list(data, true),
+            MdBlock::UList { data } => self.render_list(data, false),
+            MdBlock::Image {
+                alt_text,
+                link_text,
+                caption,
+                ..
+            } => {
+                let mut out = style("2", &format!("[image: {}] ({})", alt_text, link_text));
+                if let Some(caption) = caption {
+                    out.push('\n');
+                    out.push_str(&style("3", &self.render_inline(caption)));
+                }
+                out
+            }
+            MdBlock::Table { data } => self.render_table(data),
+            MdBlock::LineBlock(data) => data
+                .iter()
+                .map(|line| self.render_inline(line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            MdBlock::Definition { data } => self.render_definitions(data),
+            MdBlock::CodeBlock { code, .. } if code.is_empty() => String::new(),
+            MdBlock::CodeBlock { lang, attrs, code } => {
+                let tokens = lang
+                    .as_deref()
+                    .and_then(|lang| highlight(lang, code))
+                    .unwrap_or_else(|| vec![(TokenKind::Plain, code)]);
+                let first_number = first_line_number(attrs);
+                let marked = marked_lines(attrs);
+                let lines = token_lines(&tokens);
+                let width = (first_number.unwrap_or(1) + lines.len() - 1).to_string().len();
+                let mut out = vec![];
+                for (i, line) in lines.iter().enumerate() {
+                    let number = first_number.unwrap_or(1) + i;
+                    let mut text = match first_number {
+                        Some(_) => style("2", &format!("{:>width$} ", number)),
+                        None => String::new(),
+                    };
+                    // marked lines get a bar in place of the indent
+                    if marked.iter().any(|range| range.contains(&number)) {
+                        text.push_str(&style("33", "\u{258c}   "));
+                    } else {
+                        text.push_str("    ");
+                    }
+                    for (kind, part) in line {
+                        match color(*kind) {
+                            Some(code) => text.push_str(&style(code, part)),
+                            None => text.push_str(part),
+                        }
+                    }
+                    out.push(text);
+                }
+                out.join("\n")
+            }
+            MdBlock::Diagram { source, .. } => indent(source, "    "),
+            MdBlock::Math(tex) => indent(tex, "    "),
+            MdBlock::Container { attrs, children, .. } => {
+                let mut out = vec![];
+                if let Some(title) = attrs.args.first() {
+                    out.push(style("1", title));
+                }
+                out.push(self.render_blocks(children));
+                out.join("\n")
+            }
+            MdBlock::Parah(data) => self.render_inline(data),
+            MdBlock::HR => style("2", &"\u{2500}".repeat(40)),
+            MdBlock::Comment(raw) => style("2", raw),
+            MdBlock::Include { .. } | MdBlock::EmptyLine => String::new(),
+        }
+    }
+
+    fn render_list(&self, items: &[ListItem], ordered: bool) -> String {
+        let mut out = vec![];
+        for (i, item) in items.iter().enumerate() {
+            let mut marker = if ordered {
+                format!("{}. ", i + 1)
+            } else {
+                "\u{2022} ".to_string()
+            };
+            match item.task {
+                Some(true) => marker.push_str("[x] "),
+                Some(false) => marker.push_str("[ ] "),
+                None => (),
+            }
+            let mut text = format!("{}{}", marker, self.render_inline(&item.data));
+            if !item.children.is_empty() {
+                text.push('\n');
+                text.push_str(&indent(&self.render_blocks(&item.children), "  "));
+            }
+            out.push(text);
+        }
+        out.join("\n")
+    }
+
+    fn render_table(&self, rows: &[(TableRow, Vec<MdString>)]) -> String {
+        let mut out = vec![];
+        for (kind, cells) in rows {
+            let cells = cells
+                .iter()
+                .map(|cell| self.render_inline(cell))
+                .collect::<Vec<_>>()
+                .join(" \u{2502} ");
+            match kind {
+                TableRow::Heading => out.push(style("1", &cells)),
+                TableRow::Data => out.push(cells),
+            }
+        }
+        out.join("\n")
+    }
+
+    fn render_definitions(&self, items: &[DefinitionItem]) -> String {
+        let mut out = vec![];
+        for item in items {
+            for term in &item.terms {
+                out.push(style("1", &self.render_inline(term)));
+            }
+            for def in &item.defs {
+                out.push(indent(&self.render_blocks(def), "    "));
+            }
+        }
+        out.join("\n")
+    }
+
+    fn render_inline(&self, md_string: &MdString) -> String {
+        let mut out = String::new();
+        let mut inlines = md_string.iter().peekable();
+        while let Some(inline) = inlines.next() {
+            match inline {
+                MdInline::Bold(s) => out.push_str(&style("1", &self.render_inline(s))),
+                MdInline::Italic(s) => out.push_str(&style("3", &self.render_inline(s))),
+                MdInline::BoldItalic(s) => out.push_str(&style("1;3", &self.render_inline(s))),
+                MdInline::Strike(s) => out.push_str(&style("9", &self.render_inline(s))),
+                MdInline::Highlight(s) => out.push_str(&style("7", &self.render_inline(s))),
+                MdInline::Sub(s) | MdInline::Super(s) => out.push_str(&self.render_inline(s)),
+                MdInline::Code(s) => out.push_str(&style("36", &plain_text(s))),
+                MdInline::LinkText(text) => {
+                    out.push_str(&style("4", &self.render_inline(text)));
+                    if let Some(MdInline::LinkUrl(url)) = inlines.peek() {
+                        out.push_str(&style("2", &format!(" ({})", plain_text(url).trim())));
+                        inlines.next();
+                    }
+                }
+                MdInline::LinkUrl(url) => out.push_str(&format!("({})", plain_text(url))),
+                MdInline::Image { alt, url, .. } => out.push_str(&style(
+                    "2",
+                    &format!("[image: {}] ({})", plain_text(alt), url),
+                )),
+                MdInline::Footnote(label) => out.push_str(&format!("[^{}]", plain_text(label))),
+                MdInline::WikiLink {
+                    target,
+                    anchor,
+                    label,
+                    ..
+                } => {
+                    let text = wiki_text(target, anchor.as_deref(), label.as_deref());
+                    out.push_str(&style("4", &text));
+                }
+                MdInline::Math(tex) | MdInline::DisplayMath(tex) => out.push_str(tex),
+                MdInline::Emoji { emoji, .. } => out.push_str(emoji),
+                MdInline::Abbr { text, .. } => out.push_str(text),
+                MdInline::Reference(reference) => {
+                    out.push_str(&style("34", &reference.to_string()))
+                }
+                MdInline::Comment(raw) => {
+                    if self.comments {
+                        out.push_str(&style("2", raw));
+                    }
+                }
+                // nothing to show for them
+                MdInline::Attributes(_) => (),
+                MdInline::SoftBreak => out.push(' '),
+                MdInline::HardBreak => out.push('\n'),
+                MdInline::InlineString(s) => out.push_str(s),
+            }
+        }
+        out
+    }
+}
+
+/// SGR code of a token, `None` keeps the terminal's color
+fn color(kind: TokenKind) -> Option<&'static str> {
+    let code = match kind {
+        TokenKind::Keyword => "35",
+        TokenKind::Type => "33",
+        TokenKind::Constant | TokenKind::Number => "36",
+        TokenKind::String => "32",
+        TokenKind::Comment => "90",
+        TokenKind::Function | TokenKind::Property => "34",
+        TokenKind::Variable => "31",
+        TokenKind::Plain => return None,
+    };
+    Some(code)
+}
+
+///
+/// `text` in the SGR `code`, styles inside end with a reset
+/// of everything, so this one is turned on again after them.
+///
+fn style(code: &str, text: &str) -> String {
+    let start = format!("\x1b[{}m", code);
+    let text = text.replace(RESET, &format!("{}{}", RESET, start));
+    format!("{}{}{}", start, text, RESET)
+}
+
+fn indent(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", prefix, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod terminal_testing {
+    use super::TerminalRenderer;
+    use crate::parser_md;
+
+    fn md_to_term(data: &str) -> String {
+        TerminalRenderer::new().render(&parser_md(data.to_string()).blocks)
+    }
+
+    #[test]
+    fn styled_text() {
+        assert_eq!(
+            md_to_term("# Hi\n\nsome **bold** and `code`\n\n- a\n- [x] b"),
+            "\x1b[1m# Hi\x1b[0m\n\n\
+             some \x1b[1mbold\x1b[0m and \x1b[36mcode\x1b[0m\n\n\
+             \u{2022} a\n\u{2022} [x] b\n"
+        );
+    }
+
+    #[test]
+    fn nested_styles() {
+        assert_eq!(
+            md_to_term("# A `b` c\n\n**x *y* `z` tail**"),
+            "\x1b[1m# A \x1b[36mb\x1b[0m\x1b[1m c\x1b[0m\n\n\
+             \x1b[1mx \x1b[3my\x1b[0m\x1b[1m \x1b[36mz\x1b[0m\x1b[1m tail\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn wiki_links() {
+        assert_eq!(
+            md_to_term("[[#Top]] [[Page#Top]]"),
+            "\x1b[4mTop\x1b[0m \x1b[4mPage#Top\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn highlighted_code() {
+        assert_eq!(
+            md_to_term("```rust {.numbers 2}\nlet a = 1;\n// b\n```\n\n```\n<x>\n```"),
+            "\x1b[2m1 \x1b[0m    \x1b[35mlet\x1b[0m a = \x1b[36m1\x1b[0m;\n\
+             \x1b[2m2 \x1b[0m\x1b[33m\u{258c}   \x1b[0m\x1b[90m// b\x1b[0m\n\n    <x>\n"
+        );
+        assert_eq!(md_to_term("a\n\n```\n```\n\nb"), "a\n\n\n\nb\n");
+    }
+}
@@ -0,0 +1,35 @@
+///
+/// maps the target of a `WikiLink` to a url, at render time.
+///
+/// `None` means there is no such page, and the link is
+/// rendered as broken.
+///
+pub trait WikiLinkResolver {
+    fn resolve(&self, target: &str, anchor: Option<&str>) -> Option<String>;
+}
+
+impl<F> WikiLinkResolver for F
+where
+    F: Fn(&str, Option<&str>) -> Option<String>,
+{
+    fn resolve(&self, target: &str, anchor: Option<&str>) -> Option<String> {
+        self(target, anchor)
+    }
+}
+
+///
+/// links every target to a relative url of the same name,
+/// `[[My Page#Intro]]` goes to `My%20Page#Intro`.
+///
+pub struct DefaultWikiResolver;
+
+impl WikiLinkResolver for DefaultWikiResolver {
+    fn resolve(&self, target: &str, anchor: Option<&str>) -> Option<String> {
+        let mut url = target.replace(' ', "%20");
+        if let Some(anchor) = anchor {
+            url.push('#');
+            url.push_str(&anchor.replace(' ', "%20"));
+        }
+        Some(url)
+    }
+}
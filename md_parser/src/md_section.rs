@@ -0,0 +1,242 @@
+use crate::{
+    md_attributes::Attributes,
+    md_block::MdBlock,
+    md_inline_parser::{md_inline::MdInline, md_string::MdString},
+    md_renderer::html_renderer::plain_text,
+};
+
+///
+/// a heading with everything under it, until the next
+/// heading of the same or an upper level.
+///
+#[derive(Debug, PartialEq)]
+pub struct Section {
+    pub heading: MdString,
+    pub level: u8,
+    /// `{#id .class}` of the heading
+    pub attrs: Attributes,
+    /// position among the sections, `[1, 2, 3]` is `1.2.3`
+    pub number: Vec<usize>,
+    /// blocks before the first subsection
+    pub blocks: Vec<MdBlock>,
+    pub children: Vec<Section>,
+}
+
+impl Section {
+    /// number written as `1.2.3`
+    pub fn number_string(&self) -> String {
+        self.number
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    ///
+    /// `{#id}` of the heading, or else its text in lowercase with
+    /// spaces as `-` and punctuation left out, `Getting started!`
+    /// is `getting-started` and `Über` is `über`.
+    ///
+    pub fn id(&self) -> String {
+        if let Some(id) = &self.attrs.id {
+            return id.clone();
+        }
+        plain_text(&self.heading)
+            .trim()
+            .chars()
+            .flat_map(|ch| match ch {
+                ' ' | '-' => vec!['-'],
+                ch if ch.is_alphanumeric() || ch == '_' => ch.to_lowercase().collect(),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    fn into_blocks(self, numbered: bool, out: &mut Vec<MdBlock>) {
+        let number = self.number_string();
+        let mut data = self.heading;
+        if numbered {
+            let mut inlines = data.into_vec();
+            inlines.insert(0, MdInline::InlineString(format!("{} ", number)));
+            data = MdString::from_vec(inlines);
+        }
+        out.push(MdBlock::Head {
+            level: self.level,
+            attrs: self.attrs,
+            data,
+        });
+        out.extend(self.blocks);
+        for child in self.children {
+            child.into_blocks(numbered, out);
+        }
+    }
+}
+
+///
+/// blocks of a document nested in sections by their heading levels,
+/// for outlines, numbered headings or splitting it into parts.
+///
+/// a heading which skips a level, like `###` right under `#`,
+/// is still a subsection of the one before.
+///
+#[derive(Debug, PartialEq)]
+pub struct Sections {
+    /// blocks before the first heading
+    pub preamble: Vec<MdBlock>,
+    pub sections: Vec<Section>,
+}
+
+impl Sections {
+    pub fn new(blocks: Vec<MdBlock>) -> Sections {
+        let mut preamble = vec![];
+        let mut sections: Vec<Section> = vec![];
+        // sections still open, innermost last
+        let mut open: Vec<Section> = vec![];
+        for block in blocks {
+            let MdBlock::Head { level, attrs, data } = block else {
+                match open.last_mut() {
+                    Some(section) => section.blocks.push(block),
+                    None => preamble.push(block),
+                }
+                continue;
+            };
+            while open.last().is_some_and(|section| section.level >= level) {
+                close_section(&mut open, &mut sections);
+            }
+            open.push(Section {
+                heading: data,
+                level,
+                attrs,
+                number: vec![],
+                blocks: vec![],
+                children: vec![],
+            });
+        }
+        while !open.is_empty() {
+            close_section(&mut open, &mut sections);
+        }
+        let mut sections = Sections { preamble, sections };
+        sections.renumber();
+        sections
+    }
+
+    /// numbers every section again by where it is now, after they were moved around
+    pub fn renumber(&mut self) {
+        renumber(&mut self.sections, &[]);
+    }
+
+    /// section with this `id`, see `Section::id`
+    pub fn find_id(&self, id: &str) -> Option<&Section> {
+        find(&self.sections, &|section| section.id() == id)
+    }
+
+    /// section with this number, `&[1, 2]` for `1.2`
+    pub fn find_number(&self, number: &[usize]) -> Option<&Section> {
+        let (first, rest) = number.split_first()?;
+        let mut section = self.sections.get(first.checked_sub(1)?)?;
+        for n in rest {
+            section = section.children.get(n.checked_sub(1)?)?;
+        }
+        Some(section)
+    }
+
+    /// flat blocks again, with `1.2 ` before the heading text when `numbered`
+    pub fn into_blocks(self, numbered: bool) -> Vec<MdBlock> {
+        let mut out = self.preamble;
+        for section in self.sections {
+            section.into_blocks(numbered, &mut out);
+        }
+        out
+    }
+}
+
+/// innermost open section goes into the one around it
+fn close_section(open: &mut Vec<Section>, sections: &mut Vec<Section>) {
+    let Some(section) = open.pop() else {
+        return;
+    };
+    match open.last_mut() {
+        Some(parent) => parent.children.push(section),
+        None => sections.push(section),
+    }
+}
+
+fn renumber(sections: &mut [Section], parent: &[usize]) {
+    for (i, section) in sections.iter_mut().enumerate() {
+        section.number = parent.to_vec();
+        section.number.push(i + 1);
+        renumber(&mut section.children, &section.number);
+    }
+}
+
+fn find<'a>(sections: &'a [Section], is: &dyn Fn(&Section) -> bool) -> Option<&'a Section> {
+    sections.iter().find_map(|section| {
+        if is(section) {
+            Some(section)
+        } else {
+            find(&section.children, is)
+        }
+    })
+}
+
+#[cfg(test)]
+mod section_testing {
+    use super::Sections;
+    use crate::{md_renderer::html_renderer::plain_text, parser_md, HtmlRenderer};
+
+    const DOC: &str = "intro\n\n# One\n\na\n\n## Two {#two}\n\n### Three\n\n\
+                       # Four!\n\n### Skipped\n\n## Five";
+
+    #[test]
+    fn nested_and_numbered() {
+        let sections = Sections::new(parser_md(DOC.to_string()).blocks);
+        assert_eq!(sections.preamble.len(), 1);
+        let outline = |sections: &Sections| {
+            let mut outline = vec![];
+            let mut stack: Vec<_> = sections.sections.iter().rev().collect();
+            while let Some(section) = stack.pop() {
+                outline.push(format!(
+                    "{} {} {}",
+                    section.number_string(),
+                    plain_text(&section.heading),
+                    section.blocks.len()
+                ));
+                stack.extend(section.children.iter().rev());
+            }
+            outline
+        };
+        assert_eq!(
+            outline(&sections),
+            ["1 One 1", "1.1 Two 0", "1.1.1 Three 0", "2 Four! 0", "2.1 Skipped 0", "2.2 Five 0"]
+        );
+
+        let mut sections = sections;
+        let four = sections.sections.pop().unwrap();
+        sections.sections.insert(0, four);
+        sections.renumber();
+        assert_eq!(outline(&sections)[..2], ["1 Four! 0", "1.1 Skipped 0"]);
+    }
+
+    #[test]
+    fn lookup() {
+        let sections = Sections::new(parser_md(DOC.to_string()).blocks);
+        assert_eq!(sections.find_id("two").unwrap().number, [1, 1]);
+        assert_eq!(sections.find_id("four").unwrap().number, [2]);
+        assert_eq!(plain_text(&sections.find_number(&[2, 2]).unwrap().heading), "Five");
+        assert!(sections.find_number(&[1, 2]).is_none());
+        assert!(sections.find_number(&[0]).is_none());
+
+        let sections = Sections::new(parser_md("## Über uns".to_string()).blocks);
+        assert_eq!(sections.sections[0].id(), "über-uns");
+    }
+
+    #[test]
+    fn back_to_blocks() {
+        let blocks = Sections::new(parser_md("# A\n\ntext\n\n## B".to_string()).blocks)
+            .into_blocks(true);
+        assert_eq!(
+            HtmlRenderer::new().render(&blocks),
+            "<h1>1 A</h1>\n<p>text</p>\n<h2>1.1 B</h2>\n"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    md_front_matter::extract,
+    md_line_purifier::PurifiedMdLine,
+    md_line_reader::{list_marker_len, numbered_mdlines},
+    md_options::ParserOptions,
+};
+
+///
+/// a task item found in the source, with where it is.
+///
+#[derive(Debug, PartialEq)]
+pub struct Task {
+    /// text after the checkbox, as it is in the source
+    pub text: String,
+    pub done: bool,
+    /// titles of the headings this task is under, outermost first
+    pub heading_path: Vec<String>,
+    /// line number, starting from 1
+    pub line: usize,
+}
+
+///
+/// lists every task of `source`, tasks inside code blocks are left out.
+///
+pub fn list_tasks(source: &str) -> Vec<Task> {
+    find_tasks(source).into_iter().map(|(task, _)| task).collect()
+}
+
+/// tasks with the byte in their line where the task's own text starts
+fn find_tasks(source: &str) -> Vec<(Task, usize)> {
+    let options = ParserOptions::default();
+    // front matter lines are skipped, but still counted
+    let skip = extract(source).map_or(0, |(_, line_count)| line_count);
+    let lines = numbered_mdlines(
+        source.lines().skip(skip).map(String::from).collect(),
+        &options,
+    );
+
+    let mut tasks = vec![];
+    // (level, title) of headings we are under
+    let mut headings: Vec<(u8, String)> = vec![];
+    for (i, start, line) in lines {
+        match PurifiedMdLine::purify_with(line, &options) {
+            PurifiedMdLine::Head { title, level, .. } => {
+                headings.retain(|(l, _)| *l < level);
+                headings.push((level, title));
+            }
+            line => {
+                if let Some((done, text)) = find_task(&line) {
+                    let task = Task {
+                        text: text.to_owned(),
+                        done,
+                        heading_path: headings.iter().map(|(_, title)| title.clone()).collect(),
+                        line: skip + i + 1,
+                    };
+                    tasks.push((task, start));
+                }
+            }
+        }
+    }
+    tasks
+}
+
+///
+/// sets the task on `line` (starting from 1) as done or not done.
+///
+/// only the character inside `[ ]` is rewritten, everything else
+/// stays as it was. returns `None` when there is no task on `line`.
+///
+pub fn set_task(source: &str, line: usize, done: bool) -> Option<String> {
+    let offset = checkbox_offset(source, line)?;
+    let current = source.as_bytes()[offset];
+    let new = match (done, current) {
+        // already done, keep the `X` or `x` as it is
+        (true, b'x' | b'X') => return Some(source.to_owned()),
+        (true, _) => "x",
+        (false, _) => " ",
+    };
+    let mut edited = source.to_owned();
+    edited.replace_range(offset..offset + 1, new);
+    Some(edited)
+}
+
+/// flips the state of the task on `line`, see `set_task`
+pub fn toggle_task(source: &str, line: usize) -> Option<String> {
+    let task = list_tasks(source).into_iter().find(|task| task.line == line)?;
+    set_task(source, line, !task.done)
+}
+
+/// task state and text, of a line which maybe is quoted or indented
+fn find_task(line: &PurifiedMdLine) -> Option<(bool, &String)> {
+    match line {
+        PurifiedMdLine::TaskedLine {
+            task_text, done, ..
+        } => Some((*done, task_text)),
+        PurifiedMdLine::Quote { inside_md, .. } => find_task(inside_md),
+        PurifiedMdLine::TabbedLine { text, .. } => find_task(text),
+        _ => None,
+    }
+}
+
+/// byte offset in `source` of the character in between `[` and `]`
+fn checkbox_offset(source: &str, line: usize) -> Option<usize> {
+    // make sure it's a task, and not something inside code,
+    // it can start after a comment which ends on the line.
+    let (_, mut start) = find_tasks(source)
+        .into_iter()
+        .find(|(task, _)| task.line == line)?;
+
+    for raw_line in source.split_inclusive('\n').take(line - 1) {
+        start += raw_line.len();
+    }
+    let raw_line = &source[start..];
+    // skip quote markers and indentation before the list marker
+    let prefix = raw_line
+        .bytes()
+        .take_while(|b| matches!(b, b'>' | b' ' | b'\t'))
+        .count();
+    let marker = list_marker_len(&raw_line[prefix..])?;
+    Some(start + prefix + marker + 1)
+}
+
+#[cfg(test)]
+mod tasks_testing {
+    use super::*;
+
+    const TODO: &str = "# Home\n\
+                        - [ ] paint\n\
+                        ## Garden\n\
+                        * [X] water\n  \
+                          1. [x] roses\n\
+                        ```\n\
+                        - [ ] not a task\n\
+                        ```\n\
+                        # Work\n\
+                        > - [ ] mail\n";
+
+    #[test]
+    fn list_all_tasks() {
+        let tasks = list_tasks(TODO);
+        assert_eq!(
+            tasks
+                .iter()
+                .map(|task| (task.line, task.done, task.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (2, false, "paint"),
+                (4, true, "water"),
+                (5, true, "roses"),
+                (10, false, "mail"),
+            ]
+        );
+        assert_eq!(tasks[0].heading_path, vec!["Home".to_string()]);
+        assert_eq!(
+            tasks[2].heading_path,
+            vec!["Home".to_string(), "Garden".to_string()]
+        );
+        assert_eq!(tasks[3].heading_path, vec!["Work".to_string()]);
+    }
+
+    #[test]
+    fn edit_only_checkbox() {
+        let edited = toggle_task(TODO, 2).unwrap();
+        assert_eq!(edited, TODO.replacen("- [ ] paint", "- [x] paint", 1));
+
+        let edited = toggle_task(TODO, 5).unwrap();
+        assert_eq!(edited, TODO.replacen("1. [x] roses", "1. [ ] roses", 1));
+
+        let edited = set_task(TODO, 10, true).unwrap();
+        assert_eq!(edited, TODO.replacen("> - [ ] mail", "> - [x] mail", 1));
+
+        // already done stays untouched
+        assert_eq!(set_task(TODO, 4, true).unwrap(), TODO);
+
+        // not a task
+        assert_eq!(toggle_task(TODO, 1), None);
+        assert_eq!(toggle_task(TODO, 7), None);
+    }
+
+    #[test]
+    fn text_after_comment() {
+        // the line ending the comment is read as two, but it's one line
+        let tasks = list_tasks("<!-- a\nb --> c\n- [ ] task");
+        assert_eq!(tasks[0].line, 3);
+
+        let source = "<!-- a\nb -->  - [ ] x";
+        assert_eq!(list_tasks(source)[0].line, 2);
+        assert_eq!(toggle_task(source, 2).unwrap(), "<!-- a\nb -->  - [x] x");
+    }
+
+    #[test]
+    fn skip_front_matter() {
+        let source = "---\ntodo:\n- [ ] not a task\n---\n- [ ] task";
+        let tasks = list_tasks(source);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].line, 5);
+        assert_eq!(
+            toggle_task(source, 5).unwrap(),
+            source.replace("- [ ] task", "- [x] task")
+        );
+    }
+}
@@ -1,5 +1,79 @@
-use md_parser;
+use std::{env, fs, process::ExitCode};
 
-fn main() {
-    md_parser::parser_md("dsjf".to_string());
+const USAGE: &str = "usage:
+    md-parser-rust tasks <file>                 list all tasks
+    md-parser-rust tasks <file> toggle <line>   flip the task on <line>
+    md-parser-rust tasks <file> done <line>     mark the task on <line> as done
+    md-parser-rust tasks <file> undone <line>   mark the task on <line> as not done";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["tasks", file, rest @ ..] => tasks(file, rest),
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn tasks(file: &str, args: &[&str]) -> ExitCode {
+    let source = match fs::read_to_string(file) {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("{}: {}", file, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (action, line) = match args {
+        [] => {
+            for task in md_parser::list_tasks(&source) {
+                println!(
+                    "{}:{}: [{}] {}{}",
+                    file,
+                    task.line,
+                    if task.done { 'x' } else { ' ' },
+                    task.heading_path
+                        .iter()
+                        .map(|title| format!("{} > ", title))
+                        .collect::<String>(),
+                    task.text
+                );
+            }
+            return ExitCode::SUCCESS;
+        }
+        [action, line] => match line.parse::<usize>() {
+            Ok(line) => (*action, line),
+            Err(_) => {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let edited = match action {
+        "toggle" => md_parser::toggle_task(&source, line),
+        "done" => md_parser::set_task(&source, line, true),
+        "undone" => md_parser::set_task(&source, line, false),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(edited) = edited else {
+        eprintln!("{}:{}: no task on this line", file, line);
+        return ExitCode::FAILURE;
+    };
+    if let Err(err) = fs::write(file, edited) {
+        eprintln!("{}: {}", file, err);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
 }